@@ -0,0 +1,249 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use clap::{ArgGroup, Parser};
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::process;
+use std::str::FromStr;
+use sw_install_core::{JsonProgressOutput, NormalOutput};
+
+const EXTENDED_HELP: &str = include_str!("help.txt");
+
+#[derive(Parser, Debug)]
+#[command(name = "sw-install")]
+#[command(about = "Install softwarewrighter binaries to local PATH", long_about = EXTENDED_HELP)]
+#[command(disable_version_flag = true)]
+#[command(group = ArgGroup::new("target").args(["project", "here", "from_git", "tool", "self_install"]))]
+#[command(group = ArgGroup::new("path_config").args(["setup_install_dir", "refresh_path"]))]
+pub struct Args {
+    #[arg(short, long, value_name = "PATH", conflicts_with = "uninstall")]
+    pub project: Option<PathBuf>,
+    #[arg(long, conflicts_with = "uninstall")]
+    pub here: bool,
+    #[arg(long, value_name = "URL", conflicts_with = "uninstall")]
+    pub from_git: Option<String>,
+    #[arg(long, value_name = "NAME", conflicts_with = "uninstall")]
+    pub tool: Option<String>,
+    #[arg(long, conflicts_with = "uninstall")]
+    pub self_install: bool,
+    #[arg(
+        long,
+        value_name = "BRANCH",
+        requires = "from_git",
+        conflicts_with = "tag"
+    )]
+    pub branch: Option<String>,
+    #[arg(long, value_name = "TAG", requires = "from_git")]
+    pub tag: Option<String>,
+    #[arg(short, long, value_name = "NAME", requires = "target")]
+    pub rename: Option<String>,
+    #[arg(
+        long,
+        value_name = "TEMPLATE",
+        requires = "target",
+        conflicts_with = "rename"
+    )]
+    pub rename_template: Option<String>,
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires = "target",
+        conflicts_with = "rename"
+    )]
+    pub rename_map: Option<PathBuf>,
+    #[arg(long, value_name = "NAME", requires = "target", action = clap::ArgAction::Append)]
+    pub bin: Vec<String>,
+    #[arg(long, requires = "target", conflicts_with = "bin")]
+    pub all: bool,
+    #[arg(long, requires = "target")]
+    pub strict_detection: bool,
+    #[arg(long, value_name = "TYPE", requires = "target")]
+    pub r#type: Option<String>,
+    #[arg(short, long, requires = "target")]
+    pub build: bool,
+    #[arg(long, value_name = "NAME", requires = "target")]
+    pub components_dir: Option<String>,
+    #[arg(long, value_name = "NAME", requires = "target")]
+    pub component: Option<String>,
+    #[arg(long, value_name = "NAME", requires = "target", conflicts_with = "bin")]
+    pub example: Option<String>,
+    #[arg(long, value_name = "SECS", default_value_t = 2, requires = "target")]
+    pub freshness_tolerance: u64,
+    #[arg(long, requires = "target")]
+    pub allow_outdated: bool,
+    #[arg(long, value_name = "SECS", default_value_t = 3600, requires = "target")]
+    pub timeout: u64,
+    #[arg(
+        long,
+        value_name = "LAYOUT",
+        default_value = "flat",
+        requires = "target"
+    )]
+    pub layout: String,
+    #[arg(long, requires = "target")]
+    pub backup: bool,
+    #[arg(long, requires = "target")]
+    pub force: bool,
+    #[arg(long, requires = "target")]
+    pub only_if_newer: bool,
+    #[arg(long, requires = "target")]
+    pub no_executable_bit: bool,
+    #[arg(long, value_name = "N", default_value_t = 1, requires = "backup")]
+    pub keep_backups: usize,
+    #[arg(long, value_name = "N", requires = "target", conflicts_with = "backup")]
+    pub keep_old_versions: Option<usize>,
+    #[arg(short, long, value_name = "NAME", conflicts_with = "project")]
+    pub uninstall: Option<String>,
+    #[arg(long, requires = "uninstall")]
+    pub purge: bool,
+    #[arg(
+        long,
+        value_name = "NAME",
+        num_args = 0..,
+        conflicts_with_all = ["project", "here", "from_git", "uninstall"]
+    )]
+    pub update: Option<Vec<String>>,
+    #[arg(long, requires = "update")]
+    pub check: bool,
+    #[arg(long, requires = "update", conflicts_with = "check")]
+    pub rebuild: bool,
+    #[arg(long, requires = "target", conflicts_with = "from_git")]
+    pub detect: bool,
+    #[arg(short = 'l', long, conflicts_with_all = ["project", "here", "from_git", "uninstall", "update"])]
+    pub list: bool,
+    #[arg(
+        short = 's',
+        long,
+        value_name = "ORDER",
+        default_value = "name",
+        requires = "list"
+    )]
+    pub sort: String,
+    #[arg(long, value_name = "FORMAT", default_value = "text")]
+    pub format: String,
+    #[arg(long, conflicts_with = "compact")]
+    pub pretty: bool,
+    #[arg(long)]
+    pub compact: bool,
+    #[arg(long, value_name = "PATTERN", requires = "list")]
+    pub grep: Option<String>,
+    #[arg(long, value_name = "N", requires = "list")]
+    pub stale_days: Option<u64>,
+    #[arg(long, value_name = "DATE", requires = "list")]
+    pub installed_after: Option<String>,
+    #[arg(long, value_name = "DATE", requires = "list")]
+    pub installed_before: Option<String>,
+    #[arg(long, value_name = "PATH", requires = "list")]
+    pub output_file: Option<PathBuf>,
+    #[arg(long, requires = "list")]
+    pub total: bool,
+    #[arg(long, requires = "list", conflicts_with = "total")]
+    pub names_only: bool,
+    #[arg(long, requires = "list", conflicts_with = "names_only")]
+    pub long: bool,
+    #[arg(short, long, requires = "list", conflicts_with = "verbose")]
+    pub quiet: bool,
+    #[arg(long, requires = "list")]
+    pub archives: bool,
+    #[arg(long, requires = "list")]
+    pub exit_code_count: bool,
+    #[arg(
+        long,
+        requires = "list",
+        conflicts_with_all = ["output_file", "exit_code_count"]
+    )]
+    pub watch: bool,
+    #[arg(long, value_name = "SECS", requires = "watch", default_value_t = 2)]
+    pub watch_interval: u64,
+    #[arg(long, requires = "list")]
+    pub json_array: bool,
+    #[arg(
+        long,
+        value_name = "PATH",
+        requires = "list",
+        conflicts_with = "install_dir"
+    )]
+    pub dir: Option<PathBuf>,
+    #[arg(long, value_name = "KEY", requires = "list", value_parser = ["checksum"])]
+    pub dedupe_by: Option<String>,
+    #[arg(long, value_name = "KEY", requires = "list", value_parser = ["source"])]
+    pub group_by: Option<String>,
+    #[arg(long, conflicts_with_all = ["project", "uninstall", "list"])]
+    pub setup_install_dir: bool,
+    #[arg(long, requires = "setup_install_dir")]
+    pub no_path_setup: bool,
+    #[arg(long, conflicts_with_all = ["target", "uninstall", "list", "setup_install_dir"])]
+    pub refresh_path: bool,
+    #[arg(long, value_name = "NAME", value_parser = ["bash", "zsh", "fish", "sh"], requires = "path_config")]
+    pub shell: Option<String>,
+    #[arg(long, conflicts_with_all = ["project", "uninstall", "list", "setup_install_dir"])]
+    pub print_install_dir: bool,
+    #[arg(long, conflicts_with_all = ["project", "uninstall", "list", "setup_install_dir"])]
+    pub repair_manifest: bool,
+    #[arg(long, conflicts_with_all = ["target", "uninstall", "list", "update", "repair_manifest"])]
+    pub prune: bool,
+    #[arg(short = 'y', long, requires = "prune")]
+    pub yes: bool,
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["NAME", "PATH"],
+        conflicts_with_all = ["target", "uninstall", "update", "list", "unregister"]
+    )]
+    pub register: Option<Vec<String>>,
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["target", "uninstall", "update", "list"])]
+    pub unregister: Option<String>,
+    #[arg(long, value_name = "DIR")]
+    pub install_dir: Option<PathBuf>,
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        default_value = "text",
+        value_parser = ["text", "json"]
+    )]
+    pub progress_format: String,
+    #[arg(long)]
+    pub fail_on_warning: bool,
+    #[arg(short, long)]
+    pub verbose: bool,
+    #[arg(short = 'n', long)]
+    pub dry_run: bool,
+    #[arg(short = 't', long, value_name = "DIR")]
+    pub test_dir: Option<PathBuf>,
+    #[arg(short = 'V', long)]
+    pub version: bool,
+}
+
+impl Args {
+    pub fn progress_json(&self) -> bool {
+        self.progress_format == "json"
+    }
+
+    /// Output for install, uninstall and setup, honoring --progress-format.
+    pub fn output(&self) -> NormalOutput {
+        progress_output(self.verbose, self.dry_run, self.progress_json())
+    }
+}
+
+pub fn progress_output(verbose: bool, dry_run: bool, json: bool) -> NormalOutput {
+    let output = NormalOutput::new(verbose, dry_run);
+    if json {
+        output.with_handler(JsonProgressOutput)
+    } else {
+        output
+    }
+}
+
+pub fn parse_or_exit<T: FromStr>(value: &str) -> T
+where
+    T::Err: Display,
+{
+    match value.parse::<T>() {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    }
+}
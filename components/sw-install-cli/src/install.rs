@@ -1,35 +1,88 @@
 // Copyright (c) 2025 Michael A Wright
 // Licensed under the MIT License
 
-use std::process;
+use crate::args::Args;
+use std::path::Path;
+use std::process::{self, Command};
+use std::time::Duration;
 use sw_install_core::{InstallConfig, InstallError, NormalOutput};
 use sw_install_installer::Installer;
+use sw_install_manage::{GitCheckout, require_tool, run_with_timeout};
 use sw_install_validation::Validator;
 
-pub fn run(config: InstallConfig) -> Result<(), InstallError> {
-    let output = NormalOutput::new(config.verbose, config.dry_run);
+pub fn run(mut config: InstallConfig) -> Result<(), InstallError> {
+    let output = crate::args::progress_output(config.verbose, config.dry_run, config.progress_json);
+    if config.auto_profile
+        && let Some(use_debug) = Validator::new(&config, &output).auto_profile()?
+    {
+        config.use_debug = use_debug;
+    }
     let validator = Validator::new(&config, &output);
     if config.build {
         let build_dir = validator.detect_build_dir()?;
         run_cargo_build(&build_dir, &config, &output)?;
     }
     let result = validator.validate()?;
-    validate_rename(&config, result.binaries.len())?;
+    let names: Vec<String> = result.binaries.iter().map(|(n, _)| n.clone()).collect();
+    validate_rename(&config, &names)?;
+    crate::warnings::check_project_name(&config, &names, &output);
     for (name, source_path) in &result.binaries {
-        Installer::new(&config, name.clone(), source_path.clone(), &output).install()?;
+        let final_name = config.installed_name(name)?;
+        let installer = Installer::new(&config, name.clone(), source_path.clone(), &output);
+        if config.only_if_newer && installer.is_current()? {
+            output.success(&format!("{final_name} already current"));
+            continue;
+        }
+        crate::warnings::check_shell_builtin(&final_name, &output);
+        installer.install()?;
+    }
+    run_post_install(&config, &result.build_dir, &output)
+}
+
+/// Installs per `config`, or under `--detect` only prints what detection
+/// decided about the project.
+pub fn run_or_detect(args: &Args, config: InstallConfig) -> Result<(), InstallError> {
+    if !args.detect {
+        return run(config);
     }
+    let output = args.output();
+    let report = Validator::new(&config, &output).detect()?;
+    print!("{}", report.render(&config.project_path));
     Ok(())
 }
 
-fn validate_rename(config: &InstallConfig, count: usize) -> Result<(), InstallError> {
-    if config.rename.is_some() && count > 1 {
-        return Err(InstallError::RenameMultipleBinaries(count));
+/// Clones `url`, builds it in release mode, and installs from the checkout.
+pub fn run_from_git(args: &Args, url: &str) -> Result<(), InstallError> {
+    let output = args.output();
+    let reference = args.branch.as_deref().or(args.tag.as_deref());
+    if args.dry_run {
+        // The same tools GitCheckout::fetch needs, so a dry run cannot
+        // succeed where the real install would fail
+        require_tool("git")?;
+        require_tool("cargo")?;
+        output.success(&format!("Clone, build, and install from {url}"));
+        return Ok(());
+    }
+    let timeout = Duration::from_secs(args.timeout);
+    let checkout = GitCheckout::fetch(url, reference, timeout, &output)?;
+    let mut config = crate::project::install_config(args, checkout.path())?;
+    config.build = true;
+    run(config)
+}
+
+fn validate_rename(config: &InstallConfig, names: &[String]) -> Result<(), InstallError> {
+    if config.rename.is_some() && names.len() > 1 {
+        return Err(InstallError::RenameMultipleBinaries(names.len()));
+    }
+    // Reject every bad templated name before the first copy
+    for name in names {
+        config.installed_name(name)?;
     }
     Ok(())
 }
 
 fn run_cargo_build(
-    build_dir: &std::path::Path,
+    build_dir: &Path,
     config: &InstallConfig,
     output: &NormalOutput,
 ) -> Result<(), InstallError> {
@@ -41,12 +94,15 @@ fn run_cargo_build(
     if config.dry_run {
         return Ok(());
     }
-    let mut cmd = process::Command::new("cargo");
+    let mut cmd = Command::new("cargo");
     cmd.arg("build").current_dir(build_dir);
     if !config.use_debug {
         cmd.arg("--release");
     }
-    let status = cmd.status()?;
+    if let Some(ref example) = config.example {
+        cmd.args(["--example", example]);
+    }
+    let status = run_with_timeout(&mut cmd, config.command_timeout)?;
     if !status.success() {
         return Err(InstallError::BuildFailed);
     }
@@ -63,3 +119,24 @@ pub fn parse_build_type(build_type: &str) -> bool {
         }
     }
 }
+
+fn run_post_install(
+    config: &InstallConfig,
+    build_dir: &Path,
+    output: &NormalOutput,
+) -> Result<(), InstallError> {
+    let Some(ref hook) = config.post_install else {
+        return Ok(());
+    };
+    output.info(&format!("Running post-install hook: {hook}"));
+    if config.dry_run {
+        return Ok(());
+    }
+    let mut cmd = Command::new("sh");
+    cmd.args(["-c", hook]).current_dir(build_dir);
+    let status = run_with_timeout(&mut cmd, config.command_timeout)?;
+    if !status.success() {
+        return Err(InstallError::PostInstallFailed(hook.clone()));
+    }
+    Ok(())
+}
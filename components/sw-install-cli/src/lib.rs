@@ -3,8 +3,24 @@
 
 //! Re-exports for integration tests.
 
-pub use sw_install_core::{InstallConfig, InstallError, NormalOutput, format_time_ago};
-pub use sw_install_installer::{Installer, Uninstaller};
-pub use sw_install_list::{Lister, SortOrder};
-pub use sw_install_manage::Setup;
-pub use sw_install_validation::Validator;
+pub use sw_install_core::{
+    CapturedOutput, DirPolicy, INSTALL_DIR_ENV, InstallConfig, InstallConfigBuilder, InstallError,
+    InstallLayout, JSON_SCHEMA_VERSION, NormalOutput, OutputHandler, expand_env_vars, expand_path,
+    expand_tilde, format_size, format_time_ago, is_backup_name, json_string, pretty_json,
+    resolve_install_dir,
+};
+pub use sw_install_installer::{
+    InstallLock, Installer, LOCK_FILE_NAME, TempFileGuard, Uninstaller, cleanup_registered,
+};
+pub use sw_install_list::{
+    BinaryInfo, CLEAR_SCREEN, InstallDate, InvalidNamePattern, ListFormat, ListOptions, Lister,
+    Listing, NamePattern, SortOrder,
+};
+pub use sw_install_manage::{
+    GitCheckout, ManifestRepair, Setup, UpdateStatus, UpdateTarget, Updater, require_tool,
+};
+pub use sw_install_manifest::{
+    CORRUPT_SUFFIX, FileManifestStore, MANIFEST_FILE_NAME, Manifest, ManifestEntry, ManifestStore,
+    MemoryManifestStore, PruneEntry, PruneReason, RepairReport, ToolRegistry,
+};
+pub use sw_install_validation::{DetectionReport, Validator};
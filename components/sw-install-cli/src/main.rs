@@ -1,68 +1,26 @@
 // Copyright (c) 2025 Michael A Wright
 // Licensed under the MIT License
 
+mod args;
 mod install;
 mod manage;
+mod project;
+mod update;
 mod version;
+mod warnings;
 
+use args::Args;
 use clap::Parser;
-use std::path::PathBuf;
 use std::process;
-use sw_install_core::{InstallConfig, InstallError};
-
-const EXTENDED_HELP: &str = include_str!("help.txt");
-
-#[derive(Parser, Debug)]
-#[command(name = "sw-install")]
-#[command(about = "Install softwarewrighter binaries to local PATH", long_about = EXTENDED_HELP)]
-#[command(disable_version_flag = true)]
-struct Args {
-    #[arg(short, long, value_name = "PATH", conflicts_with = "uninstall")]
-    project: Option<PathBuf>,
-    #[arg(short, long, value_name = "NAME", requires = "project")]
-    rename: Option<String>,
-    #[arg(long, value_name = "NAME", requires = "project", action = clap::ArgAction::Append)]
-    bin: Vec<String>,
-    #[arg(
-        long,
-        value_name = "TYPE",
-        default_value = "release",
-        requires = "project"
-    )]
-    r#type: String,
-    #[arg(short, long, requires = "project")]
-    build: bool,
-    #[arg(short, long, value_name = "NAME", conflicts_with = "project")]
-    uninstall: Option<String>,
-    #[arg(short = 'l', long, conflicts_with_all = ["project", "uninstall"])]
-    list: bool,
-    #[arg(
-        short = 's',
-        long,
-        value_name = "ORDER",
-        default_value = "name",
-        requires = "list"
-    )]
-    sort: String,
-    #[arg(long, conflicts_with_all = ["project", "uninstall", "list"])]
-    setup_install_dir: bool,
-    #[arg(short, long)]
-    verbose: bool,
-    #[arg(short = 'n', long)]
-    dry_run: bool,
-    #[arg(short = 't', long, value_name = "DIR")]
-    test_dir: Option<PathBuf>,
-    #[arg(short = 'V', long)]
-    version: bool,
-}
+use sw_install_core::{InstallError, NormalOutput, expand_tilde};
 
 fn main() {
     let args = Args::parse();
     if args.version {
-        version::print();
+        version::print(args::parse_or_exit(&args.format), args.pretty);
         return;
     }
-    let result = dispatch(&args);
+    let result = dispatch(&args).and_then(|()| check_warnings(&args));
     if let Err(e) = result {
         eprintln!("Error: {e}");
         process::exit(1);
@@ -70,31 +28,50 @@ fn main() {
 }
 
 fn dispatch(args: &Args) -> Result<(), InstallError> {
-    if args.setup_install_dir {
-        manage::run_setup(args.verbose, args.dry_run, args.test_dir.clone())
+    if args.print_install_dir {
+        manage::run_print_install_dir(args)
+    } else if args.repair_manifest {
+        manage::run_repair_manifest(args)
+    } else if args.prune {
+        manage::run_prune(args)
+    } else if args.register.is_some() || args.unregister.is_some() {
+        manage::run_registry(args)
+    } else if args.setup_install_dir || args.refresh_path {
+        manage::run_setup(args)
     } else if args.list {
-        manage::run_list(args.verbose, &args.sort, args.test_dir.clone())
+        manage::run_list(args)
+    } else if let Some(ref names) = args.update {
+        update::run(args, names)
     } else if let Some(ref binary_name) = args.uninstall {
-        manage::run_uninstall(
-            binary_name.clone(),
-            args.verbose,
-            args.dry_run,
-            args.test_dir.clone(),
-        )
+        manage::run_uninstall(args, binary_name)
     } else if let Some(ref project_path) = args.project {
-        let use_debug = install::parse_build_type(&args.r#type);
-        let config = InstallConfig::new(
-            project_path.clone(),
-            args.rename.clone(),
-            args.bin.clone(),
-            use_debug,
-            args.verbose,
-            args.dry_run,
-            args.build,
-            args.test_dir.clone(),
-        );
-        install::run(config)
+        install::run_or_detect(
+            args,
+            project::install_config(args, &expand_tilde(project_path))?,
+        )
+    } else if let Some(ref tool) = args.tool {
+        install::run_or_detect(
+            args,
+            project::install_config(args, &project::registered_project(tool)?)?,
+        )
+    } else if args.self_install {
+        install::run_or_detect(args, project::self_install_config(args)?)
+    } else if let Some(ref url) = args.from_git {
+        install::run_from_git(args, url)
+    } else if args.here {
+        let project_path = project::find_project_root(&std::env::current_dir()?)?;
+        install::run_or_detect(args, project::install_config(args, &project_path)?)
     } else {
         Err(InstallError::NoOperationSpecified)
     }
 }
+
+/// Under `--fail-on-warning`, turns a completed operation that warned into
+/// a failure.
+fn check_warnings(args: &Args) -> Result<(), InstallError> {
+    let warnings = NormalOutput::warnings_emitted();
+    if args.fail_on_warning && warnings > 0 {
+        return Err(InstallError::WarningsAsErrors(warnings));
+    }
+    Ok(())
+}
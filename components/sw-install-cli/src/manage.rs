@@ -1,45 +1,157 @@
 // Copyright (c) 2025 Michael A Wright
 // Licensed under the MIT License
 
-use std::path::PathBuf;
+use crate::args::{Args, parse_or_exit};
+use std::fs;
+use std::io;
+use std::path::Path;
 use std::process;
-use sw_install_core::{InstallError, NormalOutput};
+use std::time::Duration;
+use sw_install_core::{InstallError, NormalOutput, expand_tilde, resolve_install_dir};
 use sw_install_installer::Uninstaller;
-use sw_install_list::{Lister, SortOrder};
-use sw_install_manage::Setup;
-
-pub fn run_setup(
-    verbose: bool,
-    dry_run: bool,
-    test_dir: Option<PathBuf>,
-) -> Result<(), InstallError> {
-    let output = NormalOutput::new(verbose, dry_run);
-    Setup::new(dry_run, test_dir, &output).setup()
+use sw_install_list::{ListFormat, ListOptions, Lister, SortOrder};
+use sw_install_manage::{ManifestRepair, Setup};
+use sw_install_manifest::{PruneReason, ToolRegistry};
+
+/// Handles `--setup-install-dir` and `--refresh-path`.
+pub fn run_setup(args: &Args) -> Result<(), InstallError> {
+    let output = args.output();
+    let setup = Setup::new(args.dry_run, args.test_dir.clone(), &output)
+        .with_install_dir(args.install_dir.clone())
+        .with_path_setup(!args.no_path_setup)
+        .with_shell(args.shell.clone());
+    if args.refresh_path {
+        let shell_config = setup.refresh_path()?;
+        output.success(&format!(
+            "PATH configuration refreshed in {}",
+            shell_config.display()
+        ));
+        return Ok(());
+    }
+    setup.setup()
+}
+
+/// Handles `--prune`, asking before removing anything unless `--yes`.
+pub fn run_prune(args: &Args) -> Result<(), InstallError> {
+    let output = NormalOutput::new(args.verbose, args.dry_run);
+    let pruned = ManifestRepair::new(args.dry_run, args.test_dir.clone(), &output)
+        .with_install_dir(args.install_dir.clone())
+        .prune(|entries| {
+            if args.yes {
+                return true;
+            }
+            for entry in entries {
+                eprintln!("  {} ({})", entry.name, entry.reason);
+            }
+            eprint!("Remove these {} entries? [y/N] ", entries.len());
+            let mut answer = String::new();
+            io::stdin().read_line(&mut answer).is_ok() && answer.trim() == "y"
+        })?;
+    for entry in &pruned {
+        output.success(&format!("Removed {} ({})", entry.name, entry.reason));
+    }
+    let dangling = pruned
+        .iter()
+        .filter(|e| e.reason == PruneReason::DanglingLink)
+        .count();
+    output.success(&format!(
+        "Pruned {} entries: {} with a missing source, {dangling} dangling symlinks",
+        pruned.len(),
+        pruned.len() - dangling
+    ));
+    Ok(())
+}
+
+pub fn run_repair_manifest(args: &Args) -> Result<(), InstallError> {
+    let output = NormalOutput::new(args.verbose, args.dry_run);
+    ManifestRepair::new(args.dry_run, args.test_dir.clone(), &output)
+        .with_install_dir(args.install_dir.clone())
+        .repair()?;
+    Ok(())
+}
+
+/// Handles `--register NAME PATH` and `--unregister NAME`.
+pub fn run_registry(args: &Args) -> Result<(), InstallError> {
+    let output = NormalOutput::new(args.verbose, args.dry_run);
+    let path = ToolRegistry::default_path()?;
+    let mut registry = ToolRegistry::load(&path)?;
+    if let Some([name, project]) = args.register.as_deref() {
+        let project = expand_tilde(Path::new(project));
+        let project =
+            fs::canonicalize(&project).map_err(|_| InstallError::ProjectNotFound(project))?;
+        output.success(&format!("Registered '{name}' -> {}", project.display()));
+        registry.register(name, project);
+    } else if let Some(ref name) = args.unregister {
+        registry
+            .unregister(name)
+            .ok_or_else(|| InstallError::UnknownTool(name.clone()))?;
+        output.success(&format!("Unregistered '{name}'"));
+    }
+    if !args.dry_run {
+        registry.save(&path)?;
+    }
+    Ok(())
+}
+
+pub fn run_print_install_dir(args: &Args) -> Result<(), InstallError> {
+    let dir = resolve_install_dir(args.install_dir.as_deref(), args.test_dir.as_deref())?;
+    println!("{}", dir.display());
+    Ok(())
 }
 
-pub fn run_list(
-    verbose: bool,
-    sort_order_str: &str,
-    test_dir: Option<PathBuf>,
-) -> Result<(), InstallError> {
-    let output = NormalOutput::new(verbose, false);
-    let sort_order = match sort_order_str.parse::<SortOrder>() {
-        Ok(order) => order,
-        Err(e) => {
-            eprintln!("Error: {e}");
-            process::exit(1);
-        }
+pub fn run_list(args: &Args) -> Result<(), InstallError> {
+    let format: ListFormat = parse_or_exit(&args.format);
+    // Verbose progress on stdout would corrupt a JSON or CSV listing
+    let to_stdout = args.output_file.is_none();
+    let output = NormalOutput::new(
+        args.verbose && !(to_stdout && format != ListFormat::Text),
+        false,
+    );
+    let sort_order: SortOrder = parse_or_exit(&args.sort);
+    let options = ListOptions {
+        format,
+        total: args.total,
+        names_only: args.names_only,
+        long: args.long,
+        quiet: args.quiet,
+        archives: args.archives,
+        dedupe: args.dedupe_by.is_some(),
+        group_by_source: args.group_by.is_some(),
+        pretty: args.pretty,
+        json_array: args.json_array,
     };
-    Lister::new(test_dir, sort_order, &output).list()?;
+    let lister = Lister::new(args.test_dir.clone(), sort_order, &output)
+        .with_install_dir(args.install_dir.clone())
+        .with_dir(args.dir.clone())
+        .with_options(options)
+        .with_output_file(args.output_file.clone())
+        .with_grep(args.grep.as_deref().map(parse_or_exit))
+        .with_stale_days(args.stale_days)
+        .with_installed_range(
+            args.installed_after.as_deref().map(parse_or_exit),
+            args.installed_before.as_deref().map(parse_or_exit),
+        );
+    if args.watch {
+        // Zero would redraw in a busy loop
+        return lister.watch(Duration::from_secs(args.watch_interval.max(1)));
+    }
+    let count = lister.print()?;
+    if args.exit_code_count {
+        // Exit statuses are a single byte
+        process::exit(count.min(255) as i32);
+    }
     Ok(())
 }
 
-pub fn run_uninstall(
-    binary_name: String,
-    verbose: bool,
-    dry_run: bool,
-    test_dir: Option<PathBuf>,
-) -> Result<(), InstallError> {
-    let output = NormalOutput::new(verbose, dry_run);
-    Uninstaller::new(binary_name, dry_run, test_dir, &output).uninstall()
+pub fn run_uninstall(args: &Args, binary_name: &str) -> Result<(), InstallError> {
+    let output = args.output();
+    Uninstaller::new(
+        binary_name.to_string(),
+        args.dry_run,
+        args.test_dir.clone(),
+        &output,
+    )
+    .with_install_dir(args.install_dir.clone())
+    .with_purge(args.purge)
+    .uninstall()
 }
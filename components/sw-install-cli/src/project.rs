@@ -0,0 +1,178 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use crate::args::Args;
+use crate::args::parse_or_exit;
+use crate::install::parse_build_type;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use sw_install_core::{InstallConfig, InstallError, expand_tilde};
+use sw_install_manifest::ToolRegistry;
+
+const PROJECT_CONFIG_FILE: &str = ".sw-install.toml";
+
+/// Defaults declared by a project's `.sw-install.toml`.
+#[derive(Debug, Default)]
+struct ProjectDefaults {
+    bin: Vec<String>,
+    rename: Option<String>,
+    profile: Option<String>,
+    post_install: Option<String>,
+}
+
+fn load_defaults(project_path: &Path) -> Result<ProjectDefaults, InstallError> {
+    let Ok(contents) = fs::read_to_string(project_path.join(PROJECT_CONFIG_FILE)) else {
+        return Ok(ProjectDefaults::default());
+    };
+    let value: toml::Value =
+        toml::from_str(&contents).map_err(|e| InstallError::ProjectConfigParse(e.to_string()))?;
+    let string = |key: &str| value.get(key).and_then(|v| v.as_str()).map(String::from);
+    let bin = match value.get("bin") {
+        Some(toml::Value::Array(names)) => names
+            .iter()
+            .filter_map(|n| n.as_str().map(String::from))
+            .collect(),
+        _ => string("bin").into_iter().collect(),
+    };
+    Ok(ProjectDefaults {
+        bin,
+        rename: string("rename"),
+        profile: string("profile"),
+        post_install: string("post_install"),
+    })
+}
+
+/// Reads a `--rename-map` file of `cargo-name = "installed-name"` pairs.
+fn load_rename_map(path: &Path) -> Result<BTreeMap<String, String>, InstallError> {
+    let parse_error = |e: String| InstallError::RenameMapParse(path.to_path_buf(), e);
+    let contents = fs::read_to_string(path).map_err(|e| parse_error(e.to_string()))?;
+    let table: toml::Table = toml::from_str(&contents).map_err(|e| parse_error(e.to_string()))?;
+    table
+        .into_iter()
+        .map(|(binary, name)| match name {
+            toml::Value::String(name) => Ok((binary, name)),
+            other => Err(parse_error(format!(
+                "'{binary}' maps to {other}, expected a string"
+            ))),
+        })
+        .collect()
+}
+
+/// Project containing `start`: the nearest Cargo.toml at or above it, or the
+/// enclosing workspace root when that package is a workspace member.
+pub fn find_project_root(start: &Path) -> Result<PathBuf, InstallError> {
+    let manifest = |dir: &Path| fs::read_to_string(dir.join("Cargo.toml")).ok();
+    let nearest = start
+        .ancestors()
+        .find(|dir| manifest(dir).is_some())
+        .ok_or_else(|| InstallError::CargoTomlNotFound(start.to_path_buf()))?;
+    let workspace = nearest
+        .ancestors()
+        .find(|dir| manifest(dir).is_some_and(|c| c.contains("[workspace]")));
+    Ok(workspace.unwrap_or(nearest).to_path_buf())
+}
+
+/// Project registered under `--tool NAME` in the tool registry.
+pub fn registered_project(name: &str) -> Result<PathBuf, InstallError> {
+    let registry = ToolRegistry::load(&ToolRegistry::default_path()?)?;
+    registry
+        .get(name)
+        .map(Path::to_path_buf)
+        .ok_or_else(|| InstallError::UnknownTool(name.to_string()))
+}
+
+/// Config for `--self-install`: the running binary from the checkout it was
+/// built in (found from its `target/` dir, keeping its build profile), else
+/// from the crate this binary was compiled from.
+pub fn self_install_config(args: &Args) -> Result<InstallConfig, InstallError> {
+    let exe = std::env::current_exe()?;
+    let compiled_from = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let built_in = sw_install_workspace::project_for_binary(&exe);
+    let project = built_in
+        .clone()
+        .or_else(|| {
+            compiled_from
+                .join("Cargo.toml")
+                .is_file()
+                .then(|| compiled_from.into())
+        })
+        .ok_or_else(|| InstallError::SelfSourceNotFound(exe.clone()))?;
+    let mut config = install_config(args, &project)?;
+    if config.bin_filter.is_empty() {
+        config.bin_filter = vec![env!("CARGO_BIN_NAME").to_string()];
+    }
+    if built_in.is_some() && exe.parent().is_some_and(|dir| dir.ends_with("debug")) {
+        config.use_debug = true;
+        config.auto_profile = false;
+    }
+    Ok(config)
+}
+
+/// `--install-dir` prefix naming the project being installed.
+const PROJECT_DIR_PREFIX: &str = "@project";
+
+/// Builds the install configuration, letting CLI flags override project defaults.
+pub fn install_config(args: &Args, project_path: &Path) -> Result<InstallConfig, InstallError> {
+    let defaults = load_defaults(project_path)?;
+    let build_type = args.r#type.clone().or(defaults.profile);
+    // --all also overrides a project-local bin selection
+    let bin = match (args.all, args.bin.is_empty()) {
+        (true, _) => Vec::new(),
+        (false, true) => defaults.bin,
+        (false, false) => args.bin.clone(),
+    };
+    let mut config = InstallConfig::new(
+        project_path.to_path_buf(),
+        args.rename.clone().or(defaults.rename),
+        bin,
+        parse_build_type(build_type.as_deref().unwrap_or("release")),
+        args.verbose,
+        args.dry_run,
+        args.build,
+        args.test_dir.clone(),
+    );
+    // Only a profile nobody asked for may be swapped for the one that exists
+    config.auto_profile = build_type.is_none() && !args.build;
+    config.rename_template = args.rename_template.clone();
+    config.install_dir = args
+        .install_dir
+        .as_deref()
+        .map(|dir| project_relative(dir, project_path));
+    config.components_dir = args.components_dir.clone();
+    config.component = args.component.clone();
+    config.example = args.example.clone();
+    config.post_install = defaults.post_install;
+    config.backup = args.backup;
+    config.keep_backups = args.keep_backups;
+    if let Some(versions) = args.keep_old_versions {
+        config.backup = versions > 0;
+        config.keep_backups = versions;
+    }
+    config.layout = parse_or_exit(&args.layout);
+    config.freshness_tolerance = Duration::from_secs(args.freshness_tolerance);
+    config.allow_outdated = args.allow_outdated;
+    config.progress_json = args.progress_json();
+    config.command_timeout = Duration::from_secs(args.timeout);
+    config.only_if_newer = args.only_if_newer;
+    config.set_executable = !args.no_executable_bit;
+    config.force = args.force;
+    config.strict_detection = args.strict_detection;
+    config.install_all = args.all;
+    if let Some(ref path) = args.rename_map {
+        config.rename_map = load_rename_map(&expand_tilde(path))?;
+    }
+    Ok(config)
+}
+
+/// Resolves an `@project/...` install dir inside the (canonical) project,
+/// for repos that keep their tools in-tree; other dirs pass through.
+fn project_relative(dir: &Path, project_path: &Path) -> PathBuf {
+    match dir.strip_prefix(PROJECT_DIR_PREFIX) {
+        Ok(rest) => fs::canonicalize(project_path)
+            .unwrap_or_else(|_| project_path.to_path_buf())
+            .join(rest),
+        Err(_) => dir.to_path_buf(),
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use crate::args::Args;
+use sw_install_core::{InstallError, NormalOutput};
+use sw_install_manage::{UpdateStatus, UpdateTarget, Updater};
+
+pub fn run(args: &Args, names: &[String]) -> Result<(), InstallError> {
+    let output = NormalOutput::new(args.verbose, args.dry_run);
+    let updater = Updater::new(names.to_vec(), args.dry_run, args.test_dir.clone(), &output)
+        .with_install_dir(args.install_dir.clone())
+        .with_verbose(args.verbose);
+    if args.check {
+        return report(&updater);
+    }
+    for target in updater.targets()? {
+        if args.rebuild {
+            rebuild(target, &output)?;
+            continue;
+        }
+        match (target.status(), target.config) {
+            (UpdateStatus::Stale, Some(config)) => crate::install::run(config)?,
+            (UpdateStatus::Current, _) => output.info(&format!("{}: up to date", target.name)),
+            (UpdateStatus::Unavailable(reason), _) => {
+                output.warn(&format!("{}: cannot update ({reason})", target.name))
+            }
+            (UpdateStatus::Stale, None) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Builds the recorded project and reinstalls from it, whatever the current
+/// status; the build runs before any copy, so a failure changes nothing.
+fn rebuild(target: UpdateTarget, output: &NormalOutput) -> Result<(), InstallError> {
+    let Some(mut config) = target.config else {
+        output.warn(&format!(
+            "{}: cannot rebuild (source project unknown)",
+            target.name
+        ));
+        return Ok(());
+    };
+    config.build = true;
+    crate::install::run(config)
+}
+
+/// Prints each binary's status; fails when any is out of date.
+fn report(updater: &Updater) -> Result<(), InstallError> {
+    let statuses = updater.check()?;
+    for (name, status) in &statuses {
+        match status {
+            UpdateStatus::Current => println!("{name}: up to date"),
+            UpdateStatus::Stale => println!("{name}: out of date"),
+            UpdateStatus::Unavailable(reason) => println!("{name}: unavailable ({reason})"),
+        }
+    }
+    let stale = statuses
+        .iter()
+        .filter(|(_, s)| *s == UpdateStatus::Stale)
+        .count();
+    if stale > 0 {
+        return Err(InstallError::UpdatesAvailable(stale));
+    }
+    Ok(())
+}
@@ -1,11 +1,19 @@
 // Copyright (c) 2025 Michael A Wright
 // Licensed under the MIT License
 
+use sw_install_core::{JSON_SCHEMA_VERSION, json_string, pretty_json};
+use sw_install_list::ListFormat;
+
 const REPOSITORY: &str = "https://github.com/softwarewrighter/sw-install";
 const LICENSE: &str = "MIT";
 const COPYRIGHT: &str = "Copyright (c) 2025 Michael A Wright";
 
-pub fn print() {
+pub fn print(format: ListFormat, pretty: bool) {
+    if format == ListFormat::Json {
+        let json = render_json();
+        println!("{}", if pretty { pretty_json(&json) } else { json });
+        return;
+    }
     println!(
         "{} {}\n{}\nLicense: {}\nRepository: {}\n\nBuild Information:\n  Host: {}\n  Commit: {}\n  Timestamp: {}",
         env!("CARGO_PKG_NAME"),
@@ -18,3 +26,24 @@ pub fn print() {
         env!("BUILD_TIMESTAMP")
     );
 }
+
+fn render_json() -> String {
+    let fields = [
+        ("name", env!("CARGO_PKG_NAME")),
+        ("version", env!("CARGO_PKG_VERSION")),
+        ("license", LICENSE),
+        ("repository", REPOSITORY),
+        ("build_host", env!("BUILD_HOST")),
+        ("git_hash", env!("GIT_HASH")),
+        ("build_timestamp", env!("BUILD_TIMESTAMP")),
+    ];
+    let body: Vec<String> = fields
+        .iter()
+        .map(|(k, v)| format!("{}:{}", json_string(k), json_string(v)))
+        .collect();
+    format!(
+        "{{\"schema_version\":{},{}}}",
+        JSON_SCHEMA_VERSION,
+        body.join(",")
+    )
+}
@@ -0,0 +1,80 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+//! Advisory checks that warn but never block an install.
+
+use sw_install_core::{InstallConfig, NormalOutput};
+use sw_install_manage::GIT_CHECKOUT_PREFIX;
+
+/// Common POSIX/bash/zsh builtins and keywords that shadow a PATH binary.
+const SHELL_BUILTINS: &[&str] = &[
+    "alias", "bg", "break", "builtin", "case", "cd", "command", "continue", "declare", "do",
+    "done", "echo", "elif", "else", "esac", "eval", "exec", "exit", "export", "false", "fc", "fg",
+    "fi", "for", "function", "getopts", "hash", "history", "if", "jobs", "kill", "let", "local",
+    "printf", "pwd", "read", "readonly", "return", "select", "set", "shift", "source", "test",
+    "then", "time", "times", "trap", "true", "type", "typeset", "ulimit", "umask", "unalias",
+    "unset", "until", "wait", "while",
+];
+
+/// Warns when `name` would be shadowed by a shell builtin or keyword.
+pub fn check_shell_builtin(name: &str, output: &NormalOutput) {
+    if SHELL_BUILTINS.contains(&name) {
+        output.warn(&format!(
+            "'{name}' is a shell builtin; running `{name}` will not reach the installed binary. Consider --rename"
+        ));
+    }
+}
+
+/// Warns when a single-binary project's directory name differs
+/// substantially from the binary it builds, which usually means `--project`
+/// points at the wrong checkout. Workspaces and multi-binary packages are
+/// skipped: their directories routinely name the suite, not any one binary.
+pub fn check_project_name(config: &InstallConfig, names: &[String], output: &NormalOutput) {
+    let [name] = names else {
+        return;
+    };
+    let project_path = config
+        .project_path
+        .canonicalize()
+        .unwrap_or_else(|_| config.project_path.clone());
+    let Some(dir_name) = project_path.file_name().and_then(|n| n.to_str()) else {
+        return;
+    };
+    // Clones and other temp dirs have names that say nothing about the project
+    if dir_name.starts_with(GIT_CHECKOUT_PREFIX) || dir_name.starts_with('.') {
+        return;
+    }
+    let installed = config.installed_name(name).ok();
+    if [Some(name), installed.as_ref()]
+        .into_iter()
+        .flatten()
+        .any(|n| names_resemble(dir_name, n))
+    {
+        return;
+    }
+    output.warn(&format!(
+        "project directory '{dir_name}' builds {name}; check that --project points at the intended project"
+    ));
+}
+
+/// True when, ignoring case, `-` and `_`, one name contains the other or
+/// the shorter abbreviates the longer: same first letter, remaining letters
+/// in order (`rg` for `ripgrep`).
+fn names_resemble(a: &str, b: &str) -> bool {
+    let normalize = |s: &str| {
+        s.chars()
+            .filter(|c| !matches!(c, '-' | '_'))
+            .flat_map(char::to_lowercase)
+            .collect::<String>()
+    };
+    let (a, b) = (normalize(a), normalize(b));
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if short.is_empty() {
+        return false;
+    }
+    if long.contains(&short) {
+        return true;
+    }
+    let mut rest = long.chars();
+    short.chars().next() == long.chars().next() && short.chars().all(|c| rest.any(|l| l == c))
+}
@@ -0,0 +1,158 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+//! Tests for backup-on-overwrite.
+
+mod common;
+
+use common::run;
+use std::fs;
+use std::path::{Path, PathBuf};
+use sw_install::{InstallConfig, Installer, Lister, NormalOutput, SortOrder, is_backup_name};
+use tempfile::TempDir;
+
+fn install(project: &Path, bin_dir: &Path, contents: &str, keep_backups: usize) -> PathBuf {
+    let target_dir = project.join("target").join("release");
+    fs::create_dir_all(&target_dir).unwrap();
+    let source = target_dir.join("app");
+    fs::write(&source, contents).unwrap();
+
+    let mut config = InstallConfig::new(
+        project.to_path_buf(),
+        None,
+        vec![],
+        false,
+        false,
+        false,
+        false,
+        Some(bin_dir.to_path_buf()),
+    );
+    config.backup = true;
+    config.keep_backups = keep_backups;
+    let output = NormalOutput::default();
+    Installer::new(&config, "app".to_string(), source, &output)
+        .install()
+        .unwrap()
+}
+
+fn backups(bin_dir: &Path) -> Vec<PathBuf> {
+    let mut found: Vec<PathBuf> = fs::read_dir(bin_dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.to_string_lossy().contains("app.bak-"))
+        .collect();
+    found.sort();
+    found
+}
+
+#[test]
+fn test_backup_keeps_old_bytes() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+
+    install(project.path(), bin_dir.path(), "old build", 1);
+    assert!(backups(bin_dir.path()).is_empty());
+
+    let dest = install(project.path(), bin_dir.path(), "new build", 1);
+    let found = backups(bin_dir.path());
+    assert_eq!(found.len(), 1);
+    assert_eq!(fs::read_to_string(&found[0]).unwrap(), "old build");
+    assert_eq!(fs::read_to_string(dest).unwrap(), "new build");
+}
+
+#[test]
+fn test_backup_prunes_to_keep_count() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+
+    for build in ["one", "two", "three", "four"] {
+        install(project.path(), bin_dir.path(), build, 2);
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+    let found = backups(bin_dir.path());
+    assert_eq!(found.len(), 2);
+    assert_eq!(fs::read_to_string(&found[0]).unwrap(), "two");
+    assert_eq!(fs::read_to_string(&found[1]).unwrap(), "three");
+}
+
+#[test]
+fn test_backups_are_excluded_from_listing() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    install(project.path(), bin_dir.path(), "old build", 1);
+    install(project.path(), bin_dir.path(), "new build", 1);
+
+    let output = NormalOutput::default();
+    let lister = Lister::new(Some(bin_dir.path().to_path_buf()), SortOrder::Name, &output);
+    let names: Vec<String> = lister
+        .collect()
+        .unwrap()
+        .into_iter()
+        .map(|b| b.name)
+        .collect();
+    assert_eq!(names, vec!["app".to_string()]);
+}
+
+#[test]
+fn test_keep_old_versions_keeps_exactly_n_archives() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    let project_arg = project.path().to_str().unwrap();
+    let bin_arg = bin_dir.path().to_str().unwrap();
+    fs::write(
+        project.path().join("Cargo.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    let target_dir = project.path().join("target").join("release");
+    fs::create_dir_all(&target_dir).unwrap();
+
+    for build in ["one", "two", "three", "four"] {
+        fs::write(target_dir.join("app"), build).unwrap();
+        let output = run(&["-p", project_arg, "-t", bin_arg, "--keep-old-versions", "2"]);
+        assert!(output.status.success(), "{output:?}");
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+    let found = backups(bin_dir.path());
+    assert_eq!(found.len(), 2);
+    assert_eq!(fs::read_to_string(&found[1]).unwrap(), "three");
+
+    let listed = |extra: &[&str]| {
+        let mut args = vec!["--list", "--names-only", "-t", bin_arg];
+        args.extend_from_slice(extra);
+        String::from_utf8(run(&args).stdout).unwrap()
+    };
+    assert_eq!(listed(&[]).lines().count(), 1);
+    assert_eq!(listed(&["--archives"]).lines().count(), 3);
+}
+
+#[test]
+fn test_only_timestamped_names_are_backups() {
+    assert!(is_backup_name("app.bak-1700000000000"));
+    assert!(!is_backup_name("db.bak-restore"));
+    assert!(!is_backup_name("app.bak-"));
+    assert!(!is_backup_name(".bak-1700000000000"));
+
+    // A real binary whose name contains the marker is listed and survives
+    // pruning the backups of the binary it starts with
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    fs::write(bin_dir.path().join("app.bak-restore"), "real binary").unwrap();
+    install(project.path(), bin_dir.path(), "old build", 1);
+    install(project.path(), bin_dir.path(), "new build", 1);
+    install(project.path(), bin_dir.path(), "newer build", 1);
+    assert!(bin_dir.path().join("app.bak-restore").exists());
+
+    let output = NormalOutput::default();
+    let lister = Lister::new(Some(bin_dir.path().to_path_buf()), SortOrder::Name, &output);
+    let names: Vec<String> = lister
+        .collect()
+        .unwrap()
+        .into_iter()
+        .map(|b| b.name)
+        .collect();
+    assert_eq!(
+        names,
+        vec!["app".to_string(), "app.bak-restore".to_string()]
+    );
+}
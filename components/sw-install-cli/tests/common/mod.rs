@@ -0,0 +1,75 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+//! Helpers shared by the integration tests.
+
+// Each test file uses only some of these
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use sw_install::{InstallConfig, Installer, NormalOutput};
+
+/// The sw-install binary under test, ready for args and env.
+pub fn sw_install() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_sw-install"))
+}
+
+pub fn run(args: &[&str]) -> Output {
+    sw_install()
+        .args(args)
+        .output()
+        .expect("failed to run sw-install")
+}
+
+/// Writes a fake release build of `name` under `project`, containing
+/// `binary <name>`, and returns its path.
+pub fn fake_build(project: &Path, name: &str) -> PathBuf {
+    let target_dir = project.join("target").join("release");
+    fs::create_dir_all(&target_dir).unwrap();
+    let source = target_dir.join(name);
+    fs::write(&source, format!("binary {name}")).unwrap();
+    source
+}
+
+/// Fake-builds `name` in `project` and installs it into `bin_dir`. Returns
+/// the build, so tests can change it after the install.
+pub fn install_fake(project: &Path, bin_dir: &Path, name: &str) -> PathBuf {
+    let source = fake_build(project, name);
+    let config = InstallConfig::new(
+        project.to_path_buf(),
+        None,
+        vec![],
+        false,
+        false,
+        false,
+        false,
+        Some(bin_dir.to_path_buf()),
+    );
+    let output = NormalOutput::default();
+    Installer::new(&config, name.to_string(), source.clone(), &output)
+        .install()
+        .unwrap();
+    source
+}
+
+/// A workspace with members `tool-a` and `tool-b`, both fake-built.
+pub fn create_two_tool_workspace(root: &Path) {
+    fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\n",
+    )
+    .unwrap();
+    for name in ["tool-a", "tool-b"] {
+        let dir = root.join("crates").join(name);
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(
+            dir.join("Cargo.toml"),
+            format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n"),
+        )
+        .unwrap();
+        fs::write(dir.join("src").join("main.rs"), "fn main() {}").unwrap();
+        fake_build(root, name);
+    }
+}
@@ -1,10 +1,16 @@
 // Copyright (c) 2025 Michael A Wright
 // Licensed under the MIT License
 
-//! Tests for the InstallConfig module.
+//! Tests for the InstallConfig module and project-local config.
 
+mod common;
+
+use common::{create_two_tool_workspace, run};
+use std::fs;
 use std::path::PathBuf;
-use sw_install::InstallConfig;
+use std::time::Duration;
+use sw_install::{InstallConfig, InstallLayout};
+use tempfile::TempDir;
 
 #[test]
 fn test_new_config() {
@@ -97,3 +103,91 @@ fn test_source_binary_path_debug() {
     let source = config.source_binary_path("myapp");
     assert_eq!(source, PathBuf::from("/test/project/target/debug/myapp"));
 }
+
+#[test]
+fn test_builder_defaults_match_new() {
+    let built = InstallConfig::builder("/tmp/project").build();
+    let positional = InstallConfig::new(
+        PathBuf::from("/tmp/project"),
+        None,
+        vec![],
+        false,
+        false,
+        false,
+        false,
+        None,
+    );
+    assert_eq!(format!("{built:?}"), format!("{positional:?}"));
+}
+
+#[test]
+fn test_builder_sets_named_fields() {
+    let config = InstallConfig::builder("/tmp/project")
+        .rename(Some("tool".to_string()))
+        .bin_filter(vec!["a".to_string()])
+        .use_debug(true)
+        .dry_run(true)
+        .test_dir(Some(PathBuf::from("/tmp/bin")))
+        .layout(InstallLayout::Nested)
+        .keep_backups(3)
+        .command_timeout(Duration::from_secs(5))
+        .build();
+
+    assert_eq!(config.project_path, PathBuf::from("/tmp/project"));
+    assert_eq!(config.rename.as_deref(), Some("tool"));
+    assert_eq!(config.bin_filter, vec!["a".to_string()]);
+    assert!(config.use_debug);
+    assert!(config.dry_run);
+    assert!(!config.verbose);
+    assert_eq!(config.test_dir, Some(PathBuf::from("/tmp/bin")));
+    assert_eq!(config.layout, InstallLayout::Nested);
+    assert_eq!(config.keep_backups, 3);
+    assert_eq!(config.command_timeout, Duration::from_secs(5));
+}
+
+#[test]
+fn test_project_local_config_selects_bin_and_runs_hook() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    create_two_tool_workspace(project.path());
+    fs::write(
+        project.path().join(".sw-install.toml"),
+        "bin = \"tool-b\"\npost_install = \"touch hook-ran\"\n",
+    )
+    .unwrap();
+
+    let output = run(&[
+        "-p",
+        project.path().to_str().unwrap(),
+        "-t",
+        bin_dir.path().to_str().unwrap(),
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(bin_dir.path().join("tool-b").exists());
+    assert!(!bin_dir.path().join("tool-a").exists());
+    assert!(project.path().join("hook-ran").exists());
+}
+
+#[test]
+fn test_cli_bin_overrides_project_local_config() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    create_two_tool_workspace(project.path());
+    fs::write(
+        project.path().join(".sw-install.toml"),
+        "bin = \"tool-b\"\n",
+    )
+    .unwrap();
+
+    let output = run(&[
+        "-p",
+        project.path().to_str().unwrap(),
+        "-t",
+        bin_dir.path().to_str().unwrap(),
+        "--bin",
+        "tool-a",
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(bin_dir.path().join("tool-a").exists());
+    assert!(!bin_dir.path().join("tool-b").exists());
+}
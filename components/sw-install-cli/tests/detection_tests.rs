@@ -0,0 +1,628 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+//! Tests for project structure detection.
+
+mod common;
+
+use common::{create_two_tool_workspace, run, sw_install};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use sw_install::{DetectionReport, InstallConfig, InstallError, NormalOutput, Validator};
+use tempfile::TempDir;
+
+fn new_config(path: PathBuf) -> InstallConfig {
+    InstallConfig::new(path, None, vec![], false, false, false, false, None)
+}
+
+fn validate(path: &Path) -> Result<Vec<String>, InstallError> {
+    let config = new_config(path.to_path_buf());
+    let output = NormalOutput::default();
+    let result = Validator::new(&config, &output).validate()?;
+    Ok(result.binaries.into_iter().map(|(n, _)| n).collect())
+}
+
+fn create_bin_component(root: &Path, dir: &str, name: &str) -> PathBuf {
+    let component = root.join(dir).join(name);
+    fs::create_dir_all(component.join("src")).unwrap();
+    fs::write(
+        component.join("Cargo.toml"),
+        format!(
+            r#"[package]
+name = "{name}"
+version = "0.1.0"
+
+[[bin]]
+name = "{name}"
+path = "src/main.rs"
+"#
+        ),
+    )
+    .unwrap();
+    fs::write(component.join("src").join("main.rs"), "fn main() {}").unwrap();
+    let target_dir = component.join("target").join("release");
+    fs::create_dir_all(&target_dir).unwrap();
+    fs::write(target_dir.join(name), "fake binary").unwrap();
+    component
+}
+
+#[test]
+fn test_cargo_toml_without_package_or_workspace_is_unsupported() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("Cargo.toml"),
+        "[dependencies]\nserde = \"1.0\"\n",
+    )
+    .unwrap();
+
+    let result = validate(temp_dir.path());
+    assert!(matches!(
+        result.unwrap_err(),
+        InstallError::UnsupportedProjectLayout(_)
+    ));
+}
+
+#[test]
+fn test_empty_components_dir_is_unsupported() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("components")).unwrap();
+
+    let result = validate(temp_dir.path());
+    assert!(matches!(
+        result.unwrap_err(),
+        InstallError::UnsupportedProjectLayout(_)
+    ));
+}
+
+#[test]
+fn test_unsupported_layout_message_lists_supported_structures() {
+    let error = InstallError::UnsupportedProjectLayout(PathBuf::from("/foo/bar"));
+    let message = error.to_string();
+    assert!(message.contains("/foo/bar"));
+    assert!(message.contains("[package]"));
+    assert!(message.contains("[workspace]"));
+    assert!(message.contains("components/"));
+}
+
+#[test]
+fn test_components_dir_override_detects_apps_layout() {
+    let temp_dir = TempDir::new().unwrap();
+    let app_dir = create_bin_component(temp_dir.path(), "apps", "my-tool");
+
+    assert!(validate(temp_dir.path()).is_err());
+
+    let mut config = new_config(temp_dir.path().to_path_buf());
+    config.components_dir = Some("apps".to_string());
+    let output = NormalOutput::default();
+    let result = Validator::new(&config, &output).validate().unwrap();
+    assert_eq!(result.binaries.len(), 1);
+    assert_eq!(result.binaries[0].0, "my-tool");
+    assert_eq!(result.build_dir, app_dir);
+}
+
+#[test]
+fn test_multi_component_picks_alphabetically_first_component() {
+    let temp_dir = TempDir::new().unwrap();
+    create_bin_component(temp_dir.path(), "components", "zeta-tool");
+    let alpha_dir = create_bin_component(temp_dir.path(), "components", "alpha-tool");
+
+    for _ in 0..3 {
+        let config = new_config(temp_dir.path().to_path_buf());
+        let output = NormalOutput::default();
+        let result = Validator::new(&config, &output).validate().unwrap();
+        assert_eq!(result.build_dir, alpha_dir);
+        assert_eq!(result.binaries[0].0, "alpha-tool");
+    }
+}
+
+#[test]
+fn test_multi_component_prefers_component_matching_project_name() {
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path().join("zeta-tool");
+    create_bin_component(&project, "components", "alpha-tool");
+    create_bin_component(&project, "components", "zeta-tool");
+
+    assert_eq!(validate(&project).unwrap(), vec!["zeta-tool".to_string()]);
+}
+
+fn validate_component(path: &Path, component: &str) -> Result<Vec<String>, InstallError> {
+    let mut config = new_config(path.to_path_buf());
+    config.component = Some(component.to_string());
+    let output = NormalOutput::default();
+    let result = Validator::new(&config, &output).validate()?;
+    Ok(result.binaries.into_iter().map(|(n, _)| n).collect())
+}
+
+#[test]
+fn test_component_selects_named_component() {
+    let temp_dir = TempDir::new().unwrap();
+    create_bin_component(temp_dir.path(), "components", "first-tool");
+    create_bin_component(temp_dir.path(), "components", "second-tool");
+
+    let names = validate_component(temp_dir.path(), "second-tool").unwrap();
+    assert_eq!(names, vec!["second-tool".to_string()]);
+}
+
+#[test]
+fn test_component_missing_is_reported() {
+    let temp_dir = TempDir::new().unwrap();
+    create_bin_component(temp_dir.path(), "components", "first-tool");
+
+    let result = validate_component(temp_dir.path(), "nope");
+    assert!(matches!(
+        result.unwrap_err(),
+        InstallError::ComponentNotFound(name) if name == "nope"
+    ));
+}
+
+#[test]
+fn test_component_without_binary_is_reported() {
+    let temp_dir = TempDir::new().unwrap();
+    let lib_dir = temp_dir.path().join("components").join("my-lib");
+    fs::create_dir_all(&lib_dir).unwrap();
+    fs::write(
+        lib_dir.join("Cargo.toml"),
+        "[package]\nname = \"my-lib\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+
+    let result = validate_component(temp_dir.path(), "my-lib");
+    assert!(matches!(
+        result.unwrap_err(),
+        InstallError::ComponentHasNoBinary(_)
+    ));
+}
+
+#[test]
+fn test_workspace_root_package_is_binary_when_members_are_libraries() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"root-tool\"\nversion = \"0.1.0\"\n\n[workspace]\nmembers = [\"crates/*\"]\n",
+    )
+    .unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src").join("main.rs"), "fn main() {}").unwrap();
+    let lib = root.join("crates").join("helper");
+    fs::create_dir_all(lib.join("src")).unwrap();
+    fs::write(
+        lib.join("Cargo.toml"),
+        "[package]\nname = \"helper\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::write(lib.join("src").join("lib.rs"), "").unwrap();
+    let target_dir = root.join("target").join("release");
+    fs::create_dir_all(&target_dir).unwrap();
+    fs::write(target_dir.join("root-tool"), "fake binary").unwrap();
+
+    assert_eq!(validate(root).unwrap(), vec!["root-tool".to_string()]);
+}
+
+#[test]
+fn test_workspace_with_library_root_and_library_members_has_no_binary() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"root-lib\"\nversion = \"0.1.0\"\n\n[workspace]\n",
+    )
+    .unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src").join("lib.rs"), "").unwrap();
+
+    assert!(matches!(
+        validate(root).unwrap_err(),
+        InstallError::BinaryNameNotFound
+    ));
+}
+
+#[test]
+fn test_same_binary_name_in_two_members_is_reported() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"server\", \"client\"]\n",
+    )
+    .unwrap();
+    for member in ["server", "client"] {
+        let dir = root.join(member);
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(
+            dir.join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"{member}\"\nversion = \"0.1.0\"\n\n[[bin]]\nname = \"cli\"\npath = \"src/main.rs\"\n"
+            ),
+        )
+        .unwrap();
+        fs::write(dir.join("src").join("main.rs"), "fn main() {}").unwrap();
+    }
+    fs::create_dir_all(root.join("target").join("release")).unwrap();
+
+    let error = validate(root).unwrap_err();
+    assert!(matches!(
+        error,
+        InstallError::DuplicateWorkspaceBinary(ref name, ref members)
+            if name == "cli" && members == "server, client"
+    ));
+    let message = error.to_string();
+    assert!(message.contains("Binary 'cli' is declared by more than one workspace member"));
+    assert!(message.contains("Hint: Install one member with --project"));
+}
+
+#[test]
+fn test_bin_name_with_slash_is_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"tool\"\nversion = \"0.1.0\"\n\n[[bin]]\nname = \"../escape\"\npath = \"src/main.rs\"\n",
+    )
+    .unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src").join("main.rs"), "fn main() {}").unwrap();
+    fs::create_dir_all(root.join("target").join("release")).unwrap();
+
+    let error = validate(root).unwrap_err();
+    assert!(matches!(error, InstallError::InvalidBinaryName(ref n) if n == "../escape"));
+}
+
+fn detect(path: &Path) -> DetectionReport {
+    let config = new_config(path.to_path_buf());
+    let output = NormalOutput::default();
+    Validator::new(&config, &output).detect().unwrap()
+}
+
+#[test]
+fn test_detect_reports_simple_project_without_requiring_build() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"tool\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src").join("main.rs"), "fn main() {}").unwrap();
+
+    let report = detect(root);
+    assert_eq!(report.project_type, "simple");
+    assert_eq!(report.component, None);
+    assert_eq!(report.build_dir, root);
+    let source = root.join("target").join("release").join("tool");
+    assert_eq!(report.binaries, vec![("tool".to_string(), source)]);
+    assert!(report.render(root).contains("(not built)"));
+}
+
+#[test]
+fn test_detect_reports_workspace_binaries() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\n",
+    )
+    .unwrap();
+    for name in ["tool-a", "tool-b"] {
+        let dir = root.join("crates").join(name);
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(
+            dir.join("Cargo.toml"),
+            format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n"),
+        )
+        .unwrap();
+        fs::write(dir.join("src").join("main.rs"), "fn main() {}").unwrap();
+    }
+
+    let report = detect(root);
+    assert_eq!(report.project_type, "workspace");
+    assert_eq!(report.component, None);
+    let names: Vec<&str> = report.binaries.iter().map(|(n, _)| n.as_str()).collect();
+    assert_eq!(names, vec!["tool-a", "tool-b"]);
+    let release = root.join("target").join("release");
+    assert!(report.binaries.iter().all(|(n, p)| *p == release.join(n)));
+}
+
+#[test]
+fn test_detect_reports_multi_component_choice() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    create_bin_component(root, "components", "beta-tool");
+    let chosen = create_bin_component(root, "components", "alpha-tool");
+
+    let report = detect(root);
+    assert_eq!(report.project_type, "multi-component");
+    assert_eq!(report.component.as_deref(), Some(chosen.as_path()));
+    assert_eq!(report.build_dir, chosen);
+    let source = chosen.join("target").join("release").join("alpha-tool");
+    assert_eq!(report.binaries, vec![("alpha-tool".to_string(), source)]);
+    let rendered = report.render(root);
+    assert!(rendered.contains("Type: multi-component"));
+    assert!(rendered.contains("Component: components/alpha-tool"));
+    assert!(!rendered.contains("(not built)"));
+}
+
+#[test]
+fn test_member_crate_resolves_binary_from_workspace_target() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\n",
+    )
+    .unwrap();
+    let member = root.join("crates").join("my-cli");
+    fs::create_dir_all(member.join("src")).unwrap();
+    fs::write(
+        member.join("Cargo.toml"),
+        "[package]\nname = \"my-cli\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::write(member.join("src").join("main.rs"), "fn main() {}").unwrap();
+    let target_dir = root.join("target").join("release");
+    fs::create_dir_all(&target_dir).unwrap();
+    fs::write(target_dir.join("my-cli"), "fake binary").unwrap();
+
+    let config = new_config(member.clone());
+    let output = NormalOutput::default();
+    let result = Validator::new(&config, &output).validate().unwrap();
+    assert_eq!(
+        result.binaries,
+        vec![("my-cli".to_string(), target_dir.join("my-cli"))]
+    );
+    assert_eq!(result.build_dir, member);
+    assert_eq!(detect(&member).project_type, "workspace-member");
+}
+
+fn validate_strict(
+    path: &Path,
+    configure: impl FnOnce(&mut InstallConfig),
+) -> Result<Vec<String>, InstallError> {
+    let mut config = new_config(path.to_path_buf());
+    config.strict_detection = true;
+    configure(&mut config);
+    let output = NormalOutput::default();
+    let result = Validator::new(&config, &output).validate()?;
+    Ok(result.binaries.into_iter().map(|(n, _)| n).collect())
+}
+
+#[test]
+fn test_strict_detection_rejects_unselected_component() {
+    let temp_dir = TempDir::new().unwrap();
+    create_bin_component(temp_dir.path(), "components", "first-tool");
+    create_bin_component(temp_dir.path(), "components", "second-tool");
+
+    let error = validate_strict(temp_dir.path(), |_| {}).unwrap_err();
+    assert!(matches!(
+        error,
+        InstallError::AmbiguousComponent(ref names) if names == "first-tool, second-tool"
+    ));
+    assert!(error.to_string().contains("--component <NAME>"));
+
+    let chosen = validate_strict(temp_dir.path(), |c| {
+        c.component = Some("second-tool".to_string())
+    });
+    assert_eq!(chosen.unwrap(), vec!["second-tool".to_string()]);
+}
+
+#[test]
+fn test_strict_detection_rejects_unselected_binaries() {
+    let temp_dir = TempDir::new().unwrap();
+    create_two_tool_workspace(temp_dir.path());
+
+    let error = validate_strict(temp_dir.path(), |_| {}).unwrap_err();
+    assert!(matches!(
+        error,
+        InstallError::AmbiguousBinaries(ref names) if names == "tool-a, tool-b"
+    ));
+
+    let picked = validate_strict(temp_dir.path(), |c| c.bin_filter = vec!["tool-b".into()]);
+    assert_eq!(picked.unwrap(), vec!["tool-b".to_string()]);
+    let all = validate_strict(temp_dir.path(), |c| c.install_all = true);
+    assert_eq!(
+        all.unwrap(),
+        vec!["tool-a".to_string(), "tool-b".to_string()]
+    );
+    assert_eq!(validate(temp_dir.path()).unwrap().len(), 2);
+}
+
+#[test]
+fn test_strict_detection_accepts_single_binary_project() {
+    let temp_dir = TempDir::new().unwrap();
+    create_bin_component(temp_dir.path(), "components", "only-tool");
+
+    assert_eq!(
+        validate_strict(temp_dir.path(), |_| {}).unwrap(),
+        vec!["only-tool".to_string()]
+    );
+}
+
+#[test]
+fn test_auto_profile_prefers_newer_build_and_ignores_missing_ones() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"tool\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src").join("main.rs"), "fn main() {}").unwrap();
+    let config = new_config(root.to_path_buf());
+    let output = NormalOutput::default();
+    let validator = Validator::new(&config, &output);
+    assert_eq!(validator.auto_profile().unwrap(), None);
+
+    let built = |profile: &str, age: u64| {
+        let dir = root.join("target").join(profile);
+        fs::create_dir_all(&dir).unwrap();
+        let file = File::create(dir.join("tool")).unwrap();
+        let mtime = SystemTime::now() - Duration::from_secs(age);
+        file.set_modified(mtime).unwrap();
+    };
+    built("debug", 60);
+    assert_eq!(validator.auto_profile().unwrap(), Some(true));
+    built("release", 120);
+    assert_eq!(validator.auto_profile().unwrap(), Some(true));
+    built("release", 0);
+    assert_eq!(validator.auto_profile().unwrap(), Some(false));
+}
+
+#[test]
+fn test_detect_prints_report_without_installing() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    create_two_tool_workspace(project.path());
+
+    let output = run(&[
+        "-p",
+        project.path().to_str().unwrap(),
+        "-t",
+        bin_dir.path().to_str().unwrap(),
+        "--detect",
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Type: workspace"));
+    assert!(stdout.contains("  tool-a -> "));
+    assert!(stdout.contains("  tool-b -> "));
+    assert!(!bin_dir.path().join("tool-a").exists());
+    assert!(!bin_dir.path().join("tool-b").exists());
+}
+
+#[test]
+fn test_here_installs_project_containing_cwd() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    create_two_tool_workspace(project.path());
+    let subdir = project.path().join("crates").join("tool-a").join("src");
+
+    let output = sw_install()
+        .args(["--here", "--bin", "tool-a", "--test-dir"])
+        .arg(bin_dir.path())
+        .current_dir(&subdir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert!(bin_dir.path().join("tool-a").exists());
+}
+
+#[test]
+fn test_here_without_cargo_project_fails() {
+    let dir = TempDir::new().unwrap();
+    let output = sw_install()
+        .arg("--here")
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Cargo.toml not found"));
+}
+
+#[test]
+fn test_project_dir_unlike_binary_name_warns() {
+    let temp = TempDir::new().unwrap();
+    let install = |dir_name: &str, extra: &[&str]| {
+        let project = temp.path().join(dir_name);
+        let bin_dir = temp.path().join(format!("{dir_name}-bin"));
+        fs::create_dir(&project).unwrap();
+        create_project_built_in(&project, "release");
+        let mut args = vec!["-p", project.to_str().unwrap(), "--test-dir"];
+        args.push(bin_dir.to_str().unwrap());
+        args.extend_from_slice(extra);
+        run(&args)
+    };
+
+    let output = install("webserver", &[]);
+    assert!(output.status.success(), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(
+        "Warning: project directory 'webserver' builds tool; check that --project points at the intended project"
+    ));
+
+    for (dir_name, extra) in [
+        ("Tool_Box", &[][..]),
+        ("tl", &[]),
+        ("gizmo", &["--rename", "gizmo"]),
+    ] {
+        let output = install(dir_name, extra);
+        assert!(output.status.success(), "{output:?}");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            !stderr.contains("project directory"),
+            "{dir_name}: {stderr}"
+        );
+    }
+}
+
+#[test]
+fn test_workspace_install_does_not_warn_about_project_name() {
+    let temp = TempDir::new().unwrap();
+    let project = temp.path().join("suite");
+    fs::create_dir(&project).unwrap();
+    create_two_tool_workspace(&project);
+    let bin_dir = temp.path().join("bin");
+
+    let output = run(&[
+        "-p",
+        project.to_str().unwrap(),
+        "--test-dir",
+        bin_dir.to_str().unwrap(),
+        "--fail-on-warning",
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("Warning"));
+}
+
+/// A built single-package project whose only binary is in `profile`.
+fn create_project_built_in(root: &Path, profile: &str) {
+    fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"tool\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src").join("main.rs"), "fn main() {}").unwrap();
+    let target = root.join("target").join(profile);
+    fs::create_dir_all(&target).unwrap();
+    fs::write(target.join("tool"), format!("{profile} binary")).unwrap();
+}
+
+#[test]
+fn test_debug_only_build_is_selected_automatically() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    create_project_built_in(project.path(), "debug");
+
+    let output = run(&[
+        "-p",
+        project.path().to_str().unwrap(),
+        "-t",
+        bin_dir.path().to_str().unwrap(),
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Using the debug build"));
+    let installed = fs::read_to_string(bin_dir.path().join("tool")).unwrap();
+    assert_eq!(installed, "debug binary");
+}
+
+#[test]
+fn test_explicit_type_disables_profile_fallback() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    create_project_built_in(project.path(), "debug");
+
+    let output = run(&[
+        "-p",
+        project.path().to_str().unwrap(),
+        "-t",
+        bin_dir.path().to_str().unwrap(),
+        "--type",
+        "release",
+    ]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No release build found"));
+    assert!(!bin_dir.path().join("tool").exists());
+}
@@ -62,3 +62,16 @@ fn test_error_no_operation_specified_mentions_all_operations() {
         "Error should mention --setup-install-dir"
     );
 }
+
+#[test]
+fn test_error_display_io_context() {
+    let error = InstallError::IoContext {
+        step: "copying binary to",
+        path: PathBuf::from("/bin/app"),
+        source: std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied"),
+    };
+    assert_eq!(
+        error.to_string(),
+        "Failed while copying binary to /bin/app: denied"
+    );
+}
@@ -0,0 +1,65 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+//! Tests for installing compiled example binaries.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use sw_install::{InstallConfig, InstallError, NormalOutput, Validator};
+use tempfile::TempDir;
+
+fn create_project(dir: &Path) {
+    fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"my-lib\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+}
+
+fn example_config(path: PathBuf, example: &str) -> InstallConfig {
+    let mut config = InstallConfig::new(path, None, vec![], false, false, false, false, None);
+    config.example = Some(example.to_string());
+    config
+}
+
+#[test]
+fn test_example_source_path_uses_examples_dir() {
+    let config = example_config(PathBuf::from("/test/project"), "demo");
+    assert_eq!(
+        config.source_binary_path("demo"),
+        PathBuf::from("/test/project/target/release/examples/demo")
+    );
+}
+
+#[test]
+fn test_validate_example_binary() {
+    let temp_dir = TempDir::new().unwrap();
+    create_project(temp_dir.path());
+    let examples_dir = temp_dir.path().join("target/release/examples");
+    fs::create_dir_all(&examples_dir).unwrap();
+    fs::write(examples_dir.join("demo"), "fake example").unwrap();
+
+    let config = example_config(temp_dir.path().to_path_buf(), "demo");
+    let output = NormalOutput::default();
+    let result = Validator::new(&config, &output).validate().unwrap();
+    assert_eq!(result.binaries.len(), 1);
+    assert_eq!(result.binaries[0].0, "demo");
+    assert_eq!(result.binaries[0].1, examples_dir.join("demo"));
+}
+
+#[test]
+fn test_missing_example_suggests_example_build() {
+    let temp_dir = TempDir::new().unwrap();
+    create_project(temp_dir.path());
+    fs::create_dir_all(temp_dir.path().join("target").join("release")).unwrap();
+
+    let config = example_config(temp_dir.path().to_path_buf(), "demo");
+    let output = NormalOutput::default();
+    let error = Validator::new(&config, &output).validate().unwrap_err();
+    assert!(matches!(error, InstallError::ExampleNotFound(ref name, _) if name == "demo"));
+    assert!(
+        error
+            .to_string()
+            .contains("cargo build --release --example demo")
+    );
+}
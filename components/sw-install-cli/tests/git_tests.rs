@@ -0,0 +1,96 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+//! Tests for the --from-git clone, build, and install pipeline.
+
+mod common;
+
+use common::sw_install;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use sw_install::{GitCheckout, InstallError, require_tool};
+use tempfile::TempDir;
+
+#[test]
+fn test_checkout_dir_is_removed_on_drop() {
+    let checkout = GitCheckout::empty().unwrap();
+    let path = checkout.path().to_path_buf();
+    assert!(path.is_dir());
+    fs::write(path.join("Cargo.toml"), "[package]").unwrap();
+    drop(checkout);
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_require_tool_reports_missing_tool() {
+    let err = require_tool("sw-install-no-such-tool").unwrap_err();
+    assert!(matches!(err, InstallError::ToolNotFound(_)));
+    assert!(err.to_string().contains("sw-install-no-such-tool"));
+}
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+fn create_repo(dir: &Path) {
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"git-tool\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    fs::write(dir.join("src").join("main.rs"), "fn main() {}\n").unwrap();
+    git(dir, &["init", "--quiet", "--initial-branch", "main"]);
+    git(dir, &["add", "."]);
+    git(dir, &["commit", "--quiet", "-m", "initial"]);
+    git(dir, &["tag", "v1"]);
+}
+
+#[test]
+fn test_from_git_clones_builds_and_installs() {
+    if require_tool("git").is_err() || require_tool("cargo").is_err() {
+        return;
+    }
+    let repo = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    create_repo(repo.path());
+    let url = format!("file://{}", repo.path().display());
+
+    let output = sw_install()
+        .args(["--from-git", &url, "--tag", "v1", "--test-dir"])
+        .arg(bin_dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert!(bin_dir.path().join("git-tool").is_file());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_from_git_dry_run_requires_cargo() {
+    if require_tool("git").is_err() {
+        return;
+    }
+    // A PATH holding git but not cargo
+    let path_dir = TempDir::new().unwrap();
+    let git_path = std::env::split_paths(&std::env::var_os("PATH").unwrap_or_default())
+        .map(|dir| dir.join("git"))
+        .find(|p| p.is_file())
+        .unwrap();
+    std::os::unix::fs::symlink(git_path, path_dir.path().join("git")).unwrap();
+
+    let output = sw_install()
+        .args(["--from-git", "file:///nowhere", "--dry-run"])
+        .env("PATH", path_dir.path())
+        .output()
+        .unwrap();
+    assert!(!output.status.success(), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cargo"));
+}
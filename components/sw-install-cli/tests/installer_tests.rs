@@ -3,9 +3,16 @@
 
 //! Tests for the Installer module.
 
+mod common;
+
+use common::{create_two_tool_workspace, fake_build, run};
 use serial_test::serial;
 use std::fs;
-use sw_install::{InstallConfig, Installer, NormalOutput};
+use std::path::{Path, PathBuf};
+use sw_install::{
+    CapturedOutput, DirPolicy, InstallConfig, InstallError, InstallLock, Installer, LOCK_FILE_NAME,
+    Manifest, NormalOutput, TempFileGuard, Validator, cleanup_registered,
+};
 use tempfile::TempDir;
 
 fn new_config(
@@ -177,3 +184,558 @@ fn test_sets_executable_permissions() {
     // Check that executable bit is set
     assert_eq!(permissions.mode() & 0o111, 0o111);
 }
+
+#[cfg(unix)]
+#[test]
+fn test_no_executable_bit_keeps_source_mode() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_project = TempDir::new().unwrap();
+    let temp_home = TempDir::new().unwrap();
+    let target_dir = temp_project.path().join("target").join("release");
+    fs::create_dir_all(&target_dir).unwrap();
+    let source_path = target_dir.join("testapp");
+    fs::write(&source_path, "fake binary").unwrap();
+    fs::set_permissions(&source_path, fs::Permissions::from_mode(0o644)).unwrap();
+
+    let config = InstallConfig::builder(temp_project.path())
+        .test_dir(Some(temp_home.path().join("bin")))
+        .set_executable(false)
+        .build();
+    let output = NormalOutput::default();
+    let installer = Installer::new(&config, "testapp".to_string(), source_path, &output);
+
+    let dest_path = installer.install().unwrap();
+    let mode = fs::metadata(&dest_path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o644);
+}
+
+#[test]
+#[serial]
+fn test_concurrent_installs_into_same_dir() {
+    let temp_project = TempDir::new().unwrap();
+    let temp_home = TempDir::new().unwrap();
+    let test_bin_dir = temp_home.path().join("bin");
+
+    let target_dir = temp_project.path().join("target").join("release");
+    fs::create_dir_all(&target_dir).unwrap();
+    let names = ["tool-a", "tool-b", "tool-c", "tool-d"];
+    for name in names {
+        fs::write(target_dir.join(name), format!("binary {name}")).unwrap();
+    }
+
+    let config = new_config(
+        temp_project.path().to_path_buf(),
+        None,
+        test_bin_dir.clone(),
+    );
+    std::thread::scope(|scope| {
+        for name in names {
+            let config = &config;
+            let source_path = target_dir.join(name);
+            scope.spawn(move || {
+                let output = NormalOutput::default();
+                Installer::new(config, name.to_string(), source_path, &output)
+                    .install()
+                    .unwrap();
+            });
+        }
+    });
+
+    for name in names {
+        let content = fs::read_to_string(test_bin_dir.join(name)).unwrap();
+        assert_eq!(content, format!("binary {name}"));
+    }
+    assert!(test_bin_dir.join(LOCK_FILE_NAME).exists());
+    // No install's manifest update was lost to another's
+    let manifest = Manifest::load(&test_bin_dir, &NormalOutput::default()).unwrap();
+    let recorded: Vec<&str> = manifest.entries().map(|e| e.name.as_str()).collect();
+    assert_eq!(recorded, names);
+}
+
+#[test]
+fn test_install_lock_is_released_on_drop() {
+    let temp_dir = TempDir::new().unwrap();
+    let first = InstallLock::acquire(temp_dir.path()).unwrap();
+    drop(first);
+    let second = InstallLock::acquire(temp_dir.path());
+    assert!(second.is_ok());
+}
+
+#[test]
+#[serial]
+fn test_cleanup_removes_registered_temp_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_file = temp_dir.path().join(".app.tmp");
+    fs::write(&temp_file, "partial copy").unwrap();
+
+    let _guard = TempFileGuard::register(&temp_file);
+    cleanup_registered();
+    assert!(!temp_file.exists());
+}
+
+#[test]
+#[serial]
+fn test_install_leaves_no_temp_file() {
+    let temp_project = TempDir::new().unwrap();
+    let temp_home = TempDir::new().unwrap();
+    let test_bin_dir = temp_home.path().join("bin");
+    let target_dir = temp_project.path().join("target").join("release");
+    fs::create_dir_all(&target_dir).unwrap();
+    fs::write(target_dir.join("testapp"), "fake binary").unwrap();
+
+    let config = new_config(
+        temp_project.path().to_path_buf(),
+        None,
+        test_bin_dir.clone(),
+    );
+    let output = NormalOutput::default();
+    let source_path = target_dir.join("testapp");
+    Installer::new(&config, "testapp".to_string(), source_path, &output)
+        .install()
+        .unwrap();
+
+    assert!(test_bin_dir.join("testapp").exists());
+    assert!(!test_bin_dir.join(".testapp.tmp").exists());
+}
+
+#[test]
+fn test_copy_failure_reports_step_and_path() {
+    let temp_project = TempDir::new().unwrap();
+    let temp_home = TempDir::new().unwrap();
+    let test_bin_dir = temp_home.path().join("bin");
+    let missing_source = temp_project.path().join("target/release/testapp");
+
+    let config = new_config(
+        temp_project.path().to_path_buf(),
+        None,
+        test_bin_dir.clone(),
+    );
+    let output = NormalOutput::default();
+    let err = Installer::new(&config, "testapp".to_string(), missing_source, &output)
+        .install()
+        .unwrap_err();
+
+    let message = err.to_string();
+    let dest = test_bin_dir.join("testapp");
+    assert!(
+        message.starts_with(&format!(
+            "Failed while copying binary to {}: ",
+            dest.display()
+        )),
+        "{message}"
+    );
+    assert!(!test_bin_dir.join(".testapp.tmp").exists());
+}
+
+#[test]
+fn test_explicit_install_dir_is_created_with_missing_parents() {
+    let temp_project = TempDir::new().unwrap();
+    let root = TempDir::new().unwrap();
+    let install_dir = root.path().join("a").join("b").join("c").join("bin");
+    let source_path = fake_build(temp_project.path(), "testapp");
+
+    let mut config = InstallConfig::new(
+        temp_project.path().to_path_buf(),
+        None,
+        vec![],
+        false,
+        false,
+        false,
+        false,
+        None,
+    );
+    config.install_dir = Some(install_dir.clone());
+    let output = NormalOutput::default();
+    let dest = Installer::new(&config, "testapp".to_string(), source_path, &output)
+        .install()
+        .unwrap();
+    assert_eq!(dest, install_dir.join("testapp"));
+    assert!(dest.exists());
+}
+
+/// Installs `testapp` with no explicit dir, so the default under `home` is used.
+fn install_to_default_dir(home: &Path, policy: DirPolicy) -> Result<PathBuf, InstallError> {
+    let temp_project = TempDir::new().unwrap();
+    let source_path = fake_build(temp_project.path(), "testapp");
+    let original_home = std::env::var_os("HOME");
+    let original_dir = std::env::var_os("SW_INSTALL_DIR");
+    unsafe {
+        std::env::set_var("HOME", home);
+        std::env::remove_var("SW_INSTALL_DIR");
+    }
+
+    let config = InstallConfig::builder(temp_project.path())
+        .dir_policy(policy)
+        .build();
+    let output = NormalOutput::default();
+    let result = Installer::new(&config, "testapp".to_string(), source_path, &output).install();
+    unsafe {
+        match original_home {
+            Some(h) => std::env::set_var("HOME", h),
+            None => std::env::remove_var("HOME"),
+        }
+        if let Some(d) = original_dir {
+            std::env::set_var("SW_INSTALL_DIR", d);
+        }
+    }
+    result
+}
+
+#[test]
+#[serial]
+fn test_default_install_dir_still_requires_setup() {
+    let home = TempDir::new().unwrap();
+    let result = install_to_default_dir(home.path(), DirPolicy::RequireSetup);
+    assert!(matches!(result, Err(InstallError::InstallDirNotFound(_))));
+    assert!(!home.path().join(".local").exists());
+}
+
+#[test]
+#[serial]
+fn test_create_policy_creates_missing_default_install_dir() {
+    let home = TempDir::new().unwrap();
+    let dest = install_to_default_dir(home.path(), DirPolicy::Create).unwrap();
+    assert!(dest.exists());
+    assert!(dest.starts_with(home.path()));
+}
+
+#[test]
+fn test_installer_output_can_be_captured() {
+    let temp_project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    let source_path = fake_build(temp_project.path(), "testapp");
+    let config = new_config(
+        temp_project.path().to_path_buf(),
+        None,
+        bin_dir.path().to_path_buf(),
+    );
+
+    let captured = sw_install::CapturedOutput::default();
+    let output = NormalOutput::new(true, false).with_handler(captured.clone());
+    Installer::new(&config, "testapp".to_string(), source_path, &output)
+        .install()
+        .unwrap();
+
+    let lines = captured.lines();
+    assert_eq!(
+        lines.first().unwrap(),
+        "[1/3] Creating destination directory..."
+    );
+    assert!(
+        lines
+            .last()
+            .unwrap()
+            .starts_with("Successfully installed: testapp -> ")
+    );
+}
+
+fn install_into(
+    install_dir: &str,
+    force: bool,
+) -> (
+    Result<std::path::PathBuf, sw_install::InstallError>,
+    Vec<String>,
+) {
+    let temp_project = TempDir::new().unwrap();
+    let source_path = fake_build(temp_project.path(), "testapp");
+    let mut config = new_config_dry_run(temp_project.path().to_path_buf(), "unused".into());
+    config.test_dir = None;
+    config.install_dir = Some(install_dir.into());
+    config.force = force;
+    let captured = sw_install::CapturedOutput::default();
+    let output = NormalOutput::new(false, true).with_handler(captured.clone());
+    let result = Installer::new(&config, "testapp".to_string(), source_path, &output).install();
+    (result, captured.lines())
+}
+
+#[test]
+fn test_system_install_dir_requires_force() {
+    let (result, _) = install_into("/usr/bin", false);
+    assert!(matches!(
+        result,
+        Err(sw_install::InstallError::SystemInstallDir(_))
+    ));
+
+    let (result, lines) = install_into("/usr/local/bin/", true);
+    assert!(result.is_ok());
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.starts_with("Warning: Installing into system directory"))
+    );
+}
+
+#[test]
+fn test_user_install_dir_does_not_warn() {
+    let root = TempDir::new().unwrap();
+    let (result, lines) = install_into(root.path().join("bin").to_str().unwrap(), false);
+    assert!(result.is_ok());
+    assert!(!lines.iter().any(|l| l.starts_with("Warning:")));
+}
+
+#[test]
+fn test_verbose_install_reports_phase_timings() {
+    let temp_project = TempDir::new().unwrap();
+    let temp_home = TempDir::new().unwrap();
+    fs::write(
+        temp_project.path().join("Cargo.toml"),
+        "[package]\nname = \"timed\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::create_dir_all(temp_project.path().join("src")).unwrap();
+    fs::write(
+        temp_project.path().join("src").join("main.rs"),
+        "fn main() {}",
+    )
+    .unwrap();
+    let target_dir = temp_project.path().join("target").join("release");
+    fs::create_dir_all(&target_dir).unwrap();
+    fs::write(target_dir.join("timed"), "fake binary").unwrap();
+
+    let mut config = new_config(
+        temp_project.path().to_path_buf(),
+        None,
+        temp_home.path().join("bin"),
+    );
+    config.verbose = true;
+    let captured = CapturedOutput::default();
+    let output = NormalOutput::new(true, false).with_handler(captured.clone());
+    let result = Validator::new(&config, &output).validate().unwrap();
+    let (name, source) = result.binaries[0].clone();
+    Installer::new(&config, name, source, &output)
+        .install()
+        .unwrap();
+
+    let lines = captured.lines();
+    for phase in ["validate", "freshness", "copy", "permissions"] {
+        let prefix = format!("Timing: {phase} took ");
+        assert!(
+            lines.iter().any(|l| l.starts_with(&prefix)),
+            "{phase}: {lines:?}"
+        );
+    }
+}
+
+#[test]
+fn test_non_verbose_install_omits_timings() {
+    let temp_project = TempDir::new().unwrap();
+    let temp_home = TempDir::new().unwrap();
+    let target_dir = temp_project.path().join("target").join("release");
+    fs::create_dir_all(&target_dir).unwrap();
+    fs::write(target_dir.join("quiet"), "fake binary").unwrap();
+
+    let config = new_config(
+        temp_project.path().to_path_buf(),
+        None,
+        temp_home.path().join("bin"),
+    );
+    let captured = CapturedOutput::default();
+    let output = NormalOutput::default().with_handler(captured.clone());
+    Installer::new(
+        &config,
+        "quiet".to_string(),
+        target_dir.join("quiet"),
+        &output,
+    )
+    .install()
+    .unwrap();
+
+    assert!(!captured.lines().iter().any(|l| l.starts_with("Timing:")));
+}
+
+#[test]
+fn test_install_refuses_directory_at_destination() {
+    let temp_project = TempDir::new().unwrap();
+    let temp_home = TempDir::new().unwrap();
+    let target_dir = temp_project.path().join("target").join("release");
+    fs::create_dir_all(&target_dir).unwrap();
+    fs::write(target_dir.join("testapp"), "fake binary").unwrap();
+    let bin_dir = temp_home.path().join("bin");
+    fs::create_dir_all(bin_dir.join("testapp")).unwrap();
+
+    let config = new_config(temp_project.path().to_path_buf(), None, bin_dir.clone());
+    let output = NormalOutput::default();
+    let error = Installer::new(
+        &config,
+        "testapp".to_string(),
+        target_dir.join("testapp"),
+        &output,
+    )
+    .install()
+    .unwrap_err();
+
+    let expected = format!(
+        "Destination {} is a directory; remove it or choose a different name",
+        bin_dir.join("testapp").display()
+    );
+    assert_eq!(error.to_string(), expected);
+    assert!(bin_dir.join("testapp").is_dir());
+}
+
+#[test]
+fn test_installing_shell_builtin_name_warns() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    create_two_tool_workspace(project.path());
+    let project_arg = project.path().to_str().unwrap();
+    let bin_arg = bin_dir.path().to_str().unwrap();
+
+    let output = run(&[
+        "-p",
+        project_arg,
+        "--bin",
+        "tool-a",
+        "-r",
+        "test",
+        "--test-dir",
+        bin_arg,
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Warning: 'test' is a shell builtin"));
+    assert!(bin_dir.path().join("test").exists());
+
+    let output = run(&["-p", project_arg, "--bin", "tool-b", "--test-dir", bin_arg]);
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("Warning"));
+}
+
+#[test]
+fn test_rename_template_renames_every_binary() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    create_two_tool_workspace(project.path());
+
+    let output = run(&[
+        "-p",
+        project.path().to_str().unwrap(),
+        "-t",
+        bin_dir.path().to_str().unwrap(),
+        "--rename-template",
+        "sw-{name}",
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(bin_dir.path().join("sw-tool-a").exists());
+    assert!(bin_dir.path().join("sw-tool-b").exists());
+    assert!(!bin_dir.path().join("tool-a").exists());
+}
+
+#[test]
+fn test_rename_map_renames_only_mapped_binary() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    create_two_tool_workspace(project.path());
+    let tool_c = project.path().join("crates").join("tool-c");
+    fs::create_dir_all(tool_c.join("src")).unwrap();
+    fs::write(
+        tool_c.join("Cargo.toml"),
+        "[package]\nname = \"tool-c\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::write(tool_c.join("src").join("main.rs"), "fn main() {}").unwrap();
+    fs::write(
+        project.path().join("target").join("release").join("tool-c"),
+        "fake binary c",
+    )
+    .unwrap();
+    let map = project.path().join("renames.toml");
+    fs::write(&map, "tool-b = \"bee\"\n").unwrap();
+
+    let output = run(&[
+        "-p",
+        project.path().to_str().unwrap(),
+        "-t",
+        bin_dir.path().to_str().unwrap(),
+        "--rename-map",
+        map.to_str().unwrap(),
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(bin_dir.path().join("tool-a").exists());
+    assert!(bin_dir.path().join("bee").exists());
+    assert!(bin_dir.path().join("tool-c").exists());
+    assert!(!bin_dir.path().join("tool-b").exists());
+}
+
+#[test]
+fn test_rename_map_rejects_non_string_values() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    create_two_tool_workspace(project.path());
+    let map = project.path().join("renames.toml");
+    fs::write(&map, "tool-a = 3\n").unwrap();
+
+    let output = run(&[
+        "-p",
+        project.path().to_str().unwrap(),
+        "-t",
+        bin_dir.path().to_str().unwrap(),
+        "--rename-map",
+        map.to_str().unwrap(),
+    ]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Could not read rename map"));
+}
+
+#[test]
+fn test_rename_template_rejects_invalid_names() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    create_two_tool_workspace(project.path());
+
+    let output = run(&[
+        "-p",
+        project.path().to_str().unwrap(),
+        "-t",
+        bin_dir.path().to_str().unwrap(),
+        "--rename-template",
+        "../{name}",
+    ]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid binary name"));
+    assert!(fs::read_dir(bin_dir.path()).unwrap().next().is_none());
+}
+
+#[test]
+fn test_timeout_kills_slow_post_install_hook() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    create_two_tool_workspace(project.path());
+    fs::write(
+        project.path().join(".sw-install.toml"),
+        "bin = \"tool-a\"\npost_install = \"exec sleep 30\"\n",
+    )
+    .unwrap();
+
+    let started = std::time::Instant::now();
+    let output = run(&[
+        "-p",
+        project.path().to_str().unwrap(),
+        "-t",
+        bin_dir.path().to_str().unwrap(),
+        "--timeout",
+        "1",
+    ]);
+    assert!(!output.status.success());
+    assert!(started.elapsed() < std::time::Duration::from_secs(20));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Command timed out after 1s: sh -c exec sleep 30"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_self_install_installs_the_running_binary() {
+    let bin_dir = TempDir::new().unwrap();
+    let output = run(&[
+        "--self-install",
+        "--test-dir",
+        bin_dir.path().to_str().unwrap(),
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    let installed = bin_dir.path().join("sw-install");
+    assert_eq!(
+        fs::read(&installed).unwrap(),
+        fs::read(env!("CARGO_BIN_EXE_sw-install")).unwrap()
+    );
+}
@@ -0,0 +1,581 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+//! Tests for the JSON list output.
+
+mod common;
+
+use common::{run, sw_install};
+use serial_test::serial;
+use std::fs;
+use sw_install::{
+    CapturedOutput, JSON_SCHEMA_VERSION, ListFormat, ListOptions, Lister, NormalOutput, SortOrder,
+    format_size, json_string, pretty_json,
+};
+use tempfile::TempDir;
+
+fn render_json(bin_dir: &std::path::Path) -> String {
+    let output = NormalOutput::default();
+    let lister = Lister::new(Some(bin_dir.to_path_buf()), SortOrder::Name, &output)
+        .with_format(ListFormat::Json);
+    lister.render(&lister.scan().unwrap())
+}
+
+#[test]
+fn test_list_format_from_str() {
+    assert_eq!("text".parse::<ListFormat>().unwrap(), ListFormat::Text);
+    assert_eq!("JSON".parse::<ListFormat>().unwrap(), ListFormat::Json);
+    assert!("yaml".parse::<ListFormat>().is_err());
+}
+
+#[test]
+fn test_json_includes_schema_version() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("app"), "fake binary").unwrap();
+
+    let json = render_json(temp_dir.path());
+    assert_eq!(JSON_SCHEMA_VERSION, 1);
+    assert!(json.starts_with(&format!("{{\"schema_version\":{JSON_SCHEMA_VERSION},")));
+    assert!(json.contains("\"name\":\"app\""));
+}
+
+fn render(bin_dir: &std::path::Path, options: ListOptions) -> String {
+    let output = NormalOutput::default();
+    let lister =
+        Lister::new(Some(bin_dir.to_path_buf()), SortOrder::Name, &output).with_options(options);
+    lister.render(&lister.scan().unwrap())
+}
+
+#[test]
+fn test_total_footer_text() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("app1"), vec![0u8; 1024]).unwrap();
+    fs::write(temp_dir.path().join("app2"), vec![0u8; 2048]).unwrap();
+
+    let options = ListOptions {
+        total: true,
+        ..ListOptions::default()
+    };
+    let text = render(temp_dir.path(), options);
+    assert_eq!(text.lines().last().unwrap(), "2 binaries, 3.0 KiB total");
+}
+
+#[test]
+fn test_total_json_aggregate_fields() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("app1"), vec![0u8; 100]).unwrap();
+    fs::write(temp_dir.path().join("app2"), vec![0u8; 50]).unwrap();
+
+    let options = ListOptions {
+        format: ListFormat::Json,
+        total: true,
+        ..ListOptions::default()
+    };
+    let json = render(temp_dir.path(), options);
+    assert!(json.contains("\"count\":2"));
+    assert!(json.contains("\"total_bytes\":150"));
+    assert!(!json.contains("binaries,"));
+}
+
+#[test]
+fn test_format_size_units() {
+    assert_eq!(format_size(512), "512 B");
+    assert_eq!(format_size(1536), "1.5 KiB");
+    assert_eq!(format_size(87 * 1024 * 1024 + 200 * 1024), "87.2 MiB");
+}
+
+/// Records every write, so tests can tell streamed output from one large
+/// write of a fully rendered document.
+#[derive(Default)]
+struct WriteRecorder {
+    data: Vec<u8>,
+    writes: Vec<usize>,
+}
+
+impl std::io::Write for WriteRecorder {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.writes.push(buf.len());
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_json_streams_many_entries() {
+    let temp_dir = TempDir::new().unwrap();
+    for i in 0..2000 {
+        fs::write(temp_dir.path().join(format!("tool-{i:04}")), "fake").unwrap();
+    }
+
+    let output = NormalOutput::default();
+    let lister = Lister::new(
+        Some(temp_dir.path().to_path_buf()),
+        SortOrder::Name,
+        &output,
+    )
+    .with_format(ListFormat::Json);
+    let listing = lister.scan().unwrap();
+    let mut out = WriteRecorder::default();
+    lister.write(&mut out, &listing).unwrap();
+
+    let json = String::from_utf8(out.data).unwrap();
+    assert_eq!(json.matches("\"name\":").count(), 2000);
+    assert!(json.contains("\"tool-0000\""));
+    assert!(json.trim_end().ends_with("]}"));
+    // Entry by entry: many writes, none anywhere near the whole document
+    assert!(out.writes.len() >= 2000, "{} writes", out.writes.len());
+    let largest = out.writes.iter().max().copied().unwrap_or_default();
+    assert!(largest < json.len() / 100, "largest write {largest} bytes");
+}
+
+#[test]
+#[serial]
+fn test_json_on_path_follows_path_env() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("app"), "fake").unwrap();
+    let original = std::env::var_os("PATH").unwrap_or_default();
+    let render_with_path = |path: &std::ffi::OsStr| {
+        unsafe { std::env::set_var("PATH", path) };
+        let output = NormalOutput::default();
+        let lister = Lister::new(
+            Some(temp_dir.path().to_path_buf()),
+            SortOrder::Name,
+            &output,
+        )
+        .with_format(ListFormat::Json);
+        lister.render(&lister.scan().unwrap())
+    };
+
+    let mut dirs: Vec<_> = std::env::split_paths(&original).collect();
+    let without = render_with_path(&std::env::join_paths(&dirs).unwrap());
+    dirs.insert(0, temp_dir.path().to_path_buf());
+    let with = render_with_path(&std::env::join_paths(&dirs).unwrap());
+    unsafe { std::env::set_var("PATH", &original) };
+
+    assert!(without.contains("\"on_path\":false"));
+    assert!(with.contains("\"on_path\":true"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_owner_is_current_user() {
+    use std::os::unix::fs::MetadataExt;
+    let temp_dir = TempDir::new().unwrap();
+    let app = temp_dir.path().join("app");
+    fs::write(&app, "fake").unwrap();
+    let uid = fs::metadata(&app).unwrap().uid().to_string();
+    let user = std::process::Command::new("id")
+        .arg("-un")
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .unwrap_or_default();
+
+    let json = render_json(temp_dir.path());
+    let long = render(
+        temp_dir.path(),
+        ListOptions {
+            long: true,
+            ..ListOptions::default()
+        },
+    );
+    let owner = [user, uid]
+        .into_iter()
+        .find(|o| !o.is_empty() && json.contains(&format!("\"owner\":\"{o}\"")))
+        .expect("owner should be the current user or UID");
+    assert!(long.contains(&format!("  {owner}  ")));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_owner_is_resolved_only_for_long_and_json_output() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("app"), "fake").unwrap();
+    let owner = |options: ListOptions| {
+        let output = NormalOutput::default();
+        Lister::new(
+            Some(temp_dir.path().to_path_buf()),
+            SortOrder::Name,
+            &output,
+        )
+        .with_options(options)
+        .collect()
+        .unwrap()[0]
+            .owner
+            .clone()
+    };
+
+    assert_eq!(owner(ListOptions::default()), None);
+    let csv = ListOptions {
+        format: ListFormat::Csv,
+        ..ListOptions::default()
+    };
+    assert_eq!(owner(csv), None);
+    let long = ListOptions {
+        long: true,
+        ..ListOptions::default()
+    };
+    assert!(owner(long).is_some());
+    let json = ListOptions {
+        format: ListFormat::Json,
+        ..ListOptions::default()
+    };
+    assert!(owner(json).is_some());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_binaries_owned_by_another_user_are_flagged() {
+    use std::os::unix::fs::MetadataExt;
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("mine"), "fake").unwrap();
+    let theirs = temp_dir.path().join("theirs");
+    fs::write(&theirs, "fake").unwrap();
+    let uid = fs::metadata(&theirs).unwrap().uid();
+    if std::os::unix::fs::chown(&theirs, Some(uid + 1), None).is_err() {
+        return; // Only root can give a file away
+    }
+
+    let captured = CapturedOutput::default();
+    let output = NormalOutput::default().with_handler(captured.clone());
+    let lister = Lister::new(
+        Some(temp_dir.path().to_path_buf()),
+        SortOrder::Name,
+        &output,
+    )
+    .with_format(ListFormat::Json);
+    let json = lister.render(&lister.scan().unwrap());
+    let entry = |name: &str| {
+        let start = json.find(&format!("\"name\":\"{name}\"")).unwrap();
+        json[start..].split('}').next().unwrap().to_string()
+    };
+    assert!(entry("mine").contains("\"foreign_owner\":false"));
+    assert!(entry("theirs").contains("\"foreign_owner\":true"));
+    // Collecting has no side effects; printing the listing warns once
+    assert!(captured.lines().is_empty());
+    let listing = temp_dir.path().join("listing.json");
+    lister.with_output_file(Some(listing)).print().unwrap();
+    let warnings = captured.lines();
+    assert_eq!(warnings.len(), 1, "{warnings:?}");
+    assert!(warnings[0].starts_with("Warning: theirs is owned by "));
+    assert!(warnings[0].ends_with(", not the current user"));
+
+    let long = render(
+        temp_dir.path(),
+        ListOptions {
+            long: true,
+            ..ListOptions::default()
+        },
+    );
+    let line = |name: &str| long.lines().find(|l| l.starts_with(name)).unwrap();
+    assert!(line("theirs").ends_with(" (other owner)"));
+    assert!(!line("mine").contains("(other owner)"));
+}
+
+#[test]
+fn test_csv_has_header_and_quotes_names() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("app"), "fake").unwrap();
+    fs::write(temp_dir.path().join("odd,name"), "fake").unwrap();
+
+    let output = NormalOutput::default();
+    let lister = Lister::new(
+        Some(temp_dir.path().to_path_buf()),
+        SortOrder::Name,
+        &output,
+    )
+    .with_format("csv".parse().unwrap());
+    let csv = lister.render(&lister.scan().unwrap());
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(lines[0], "name,size_bytes,modified_iso,source");
+    assert!(lines[1].starts_with("app,4,"), "{csv}");
+    assert!(lines[1].ends_with("Z,"), "{csv}");
+    assert!(lines[2].starts_with("\"odd,name\",4,"), "{csv}");
+}
+
+/// The unquoted integer following `"key":` in `json`, if there is one.
+fn integer_field(json: &str, key: &str) -> Option<u64> {
+    let start = json.find(&format!("\"{key}\":"))? + key.len() + 3;
+    let digits: String = json[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse().ok()
+}
+
+#[test]
+fn test_json_size_and_mtime_are_integers() {
+    let temp_dir = TempDir::new().unwrap();
+    let app = temp_dir.path().join("app");
+    fs::write(&app, "12345").unwrap();
+    let stamp = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000);
+    fs::File::options()
+        .write(true)
+        .open(&app)
+        .unwrap()
+        .set_modified(stamp)
+        .unwrap();
+
+    let json = render_json(temp_dir.path());
+    assert_eq!(integer_field(&json, "size_bytes"), Some(5));
+    assert_eq!(
+        integer_field(&json, "modified_unix_secs"),
+        Some(1_700_000_000)
+    );
+    assert!(json.contains("\"modified_ago\":\""));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_json_reports_symlinked_entries() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = TempDir::new().unwrap();
+    let real = store.path().join("real-tool");
+    fs::write(&real, "fake binary").unwrap();
+    std::os::unix::fs::symlink(&real, temp_dir.path().join("linked")).unwrap();
+    fs::write(temp_dir.path().join("plain"), "fake binary").unwrap();
+
+    let json = render_json(temp_dir.path());
+    let target = fs::canonicalize(&real).unwrap();
+    assert!(json.contains(&format!(
+        "\"is_symlink\":true,\"link_target\":\"{}\",\"broken_symlink\":false",
+        target.display()
+    )));
+    assert!(json.contains("\"is_symlink\":false,\"link_target\":null,\"broken_symlink\":false"));
+}
+
+fn render_list_json(bin_dir: &std::path::Path, pretty: bool) -> String {
+    let options = ListOptions {
+        format: ListFormat::Json,
+        pretty,
+        ..ListOptions::default()
+    };
+    render(bin_dir, options)
+}
+
+#[test]
+fn test_json_is_compact_by_default_and_pretty_on_request() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("app"), "fake binary").unwrap();
+
+    let compact = render_list_json(temp_dir.path(), false);
+    assert_eq!(compact.trim_end().lines().count(), 1);
+    assert!(compact.contains("\"name\":\"app\",\"size_bytes\":11"));
+
+    let pretty = render_list_json(temp_dir.path(), true);
+    assert!(pretty.starts_with("{\n  \"schema_version\": 1,\n  \"install_dir\": "));
+    assert!(pretty.contains(",\n  \"count\": 1,\n  \"entries\": [\n    {\n"));
+    assert!(pretty.contains("\n      \"name\": \"app\",\n      \"size_bytes\": 11,\n"));
+    assert!(pretty.ends_with("\n    }\n  ],\n  \"errors\": []\n}\n"));
+}
+
+#[test]
+fn test_pretty_json_keeps_strings_and_empty_arrays() {
+    let pretty = pretty_json(r#"{"a":"x,{y}:\"z\"","b":[]}"#);
+    assert_eq!(pretty, "{\n  \"a\": \"x,{y}:\\\"z\\\"\",\n  \"b\": []\n}");
+}
+
+#[test]
+fn test_version_json_pretty_flag() {
+    let run = |flag: &str| {
+        let output = sw_install()
+            .args(["--version", "--format", "json", flag])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+        String::from_utf8(output.stdout).unwrap()
+    };
+    assert_eq!(run("--compact").lines().count(), 1);
+    let pretty = run("--pretty");
+    assert!(pretty.lines().count() > 5);
+    assert!(pretty.contains("\n  \"name\": \"sw-install\",\n"));
+}
+
+#[test]
+fn test_json_reports_install_dir_and_count() {
+    let temp_dir = TempDir::new().unwrap();
+    for name in ["one", "two", "three"] {
+        fs::write(temp_dir.path().join(name), "fake binary").unwrap();
+    }
+
+    let json = render_json(temp_dir.path());
+    let dir = json_string(&temp_dir.path().display().to_string());
+    assert!(json.starts_with(&format!(
+        "{{\"schema_version\":{JSON_SCHEMA_VERSION},\"install_dir\":{dir},\"count\":3,\"entries\":[{{"
+    )));
+    assert_eq!(json.matches("\"name\":").count(), 3);
+}
+
+#[test]
+fn test_json_array_emits_bare_entries() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("app"), "fake binary").unwrap();
+
+    let options = ListOptions {
+        format: ListFormat::Json,
+        json_array: true,
+        ..ListOptions::default()
+    };
+    let json = render(temp_dir.path(), options);
+    assert!(json.starts_with("[{\"name\":\"app\","));
+    assert!(json.ends_with("}]\n"));
+    assert!(!json.contains("schema_version"));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_json_lists_readable_entries_and_reports_unreadable_ones() {
+    use std::os::unix::fs::PermissionsExt;
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("app"), "fake binary").unwrap();
+    let dangling = temp_dir.path().join("gone");
+    std::os::unix::fs::symlink(temp_dir.path().join("missing"), &dangling).unwrap();
+
+    let json = render_json(temp_dir.path());
+    assert!(json.contains("\"count\":1,"));
+    assert!(json.contains("\"name\":\"app\""));
+    let gone = json_string(&dangling.display().to_string());
+    assert!(json.contains(&format!(",\"errors\":[{{\"path\":{gone},\"error\":")));
+
+    let locked = temp_dir.path().join("locked");
+    fs::create_dir(&locked).unwrap();
+    fs::write(locked.join("locked"), "fake binary").unwrap();
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+    let readable = fs::metadata(locked.join("locked")).is_ok();
+    let json = render_json(temp_dir.path());
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+    if readable {
+        return; // Running as root: permission bits are not enforced
+    }
+    assert!(json.contains("\"count\":1,"));
+    let locked = json_string(&locked.join("locked").display().to_string());
+    assert!(json.contains(&format!("{{\"path\":{locked},\"error\":")));
+    assert!(json.contains("Permission denied"));
+}
+
+#[test]
+fn test_unreadable_entries_warn_once_per_listing() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("app"), "fake binary").unwrap();
+    std::os::unix::fs::symlink(
+        temp_dir.path().join("missing"),
+        temp_dir.path().join("gone"),
+    )
+    .unwrap();
+    let captured = CapturedOutput::default();
+    let output = NormalOutput::default().with_handler(captured.clone());
+    let lister = Lister::new(
+        Some(temp_dir.path().to_path_buf()),
+        SortOrder::Name,
+        &output,
+    );
+
+    // Scanning and rendering, as each watch frame does, stay quiet
+    let listing = lister.scan().unwrap();
+    assert_eq!(listing.unreadable.len(), 1);
+    lister.render(&listing);
+    lister.render(&listing);
+    assert!(captured.lines().is_empty());
+
+    let listing_file = temp_dir.path().join("out").join("listing.txt");
+    lister.with_output_file(Some(listing_file)).print().unwrap();
+    let warnings = captured.lines();
+    assert_eq!(warnings.len(), 1, "{warnings:?}");
+    assert!(warnings[0].contains("Could not read"));
+}
+
+#[test]
+fn test_arch_is_read_from_elf_header() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut elf = vec![0u8; 64];
+    elf[..4].copy_from_slice(b"\x7fELF");
+    elf[4] = 2; // 64-bit
+    elf[5] = 1; // little-endian
+    elf[18..20].copy_from_slice(&0xb7u16.to_le_bytes());
+    fs::write(temp_dir.path().join("arm-tool"), &elf).unwrap();
+    fs::write(temp_dir.path().join("script"), "#!/bin/sh\n").unwrap();
+
+    let json = render_json(temp_dir.path());
+    assert!(json.contains("\"name\":\"arm-tool\""));
+    assert!(json.contains("\"arch\":\"aarch64\""));
+    assert!(json.contains("\"arch\":\"unknown\""));
+
+    let long = render(
+        temp_dir.path(),
+        ListOptions {
+            long: true,
+            ..ListOptions::default()
+        },
+    );
+    let line = long.lines().find(|l| l.starts_with("arm-tool")).unwrap();
+    assert!(line.contains("  aarch64  "));
+
+    // Plain listings never show the arch, so no header is read for them
+    let output = NormalOutput::default();
+    let lister = Lister::new(
+        Some(temp_dir.path().to_path_buf()),
+        SortOrder::Name,
+        &output,
+    );
+    assert!(lister.collect().unwrap().iter().all(|b| b.arch.is_none()));
+}
+
+#[test]
+fn test_version_json_includes_schema_version() {
+    let output = run(&["--version", "--format", "json"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(&format!("\"schema_version\":{JSON_SCHEMA_VERSION}")));
+    assert!(stdout.contains("\"name\":\"sw-install\""));
+}
+
+#[test]
+fn test_list_output_file_writes_json_instead_of_stdout() {
+    let bin_dir = TempDir::new().unwrap();
+    let reports = TempDir::new().unwrap();
+    fs::write(bin_dir.path().join("app"), "fake binary").unwrap();
+    let report = reports.path().join("ci").join("inventory.json");
+
+    let output = run(&[
+        "--list",
+        "--format",
+        "json",
+        "-t",
+        bin_dir.path().to_str().unwrap(),
+        "--output-file",
+        report.to_str().unwrap(),
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(output.stdout.is_empty());
+    let json = fs::read_to_string(&report).unwrap();
+    assert!(json.starts_with(&format!("{{\"schema_version\":{JSON_SCHEMA_VERSION},")));
+    assert!(json.contains("\"count\":1,"));
+    assert!(json.contains("\"name\":\"app\","));
+}
+
+#[test]
+fn test_empty_dir_json_listing_is_a_bare_document_even_verbose() {
+    let bin_dir = TempDir::new().unwrap();
+    let dir = bin_dir.path().to_str().unwrap();
+
+    for extra in [&[][..], &["-v"], &["--json-array"], &["--quiet"]] {
+        let mut args = vec!["--list", "--format", "json", "-t", dir];
+        args.extend_from_slice(extra);
+        let output = run(&args);
+        assert!(output.status.success(), "{output:?}");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let expected = if extra == ["--json-array"] {
+            "[]\n".to_string()
+        } else {
+            format!(
+                "{{\"schema_version\":{JSON_SCHEMA_VERSION},\"install_dir\":{},\"count\":0,\"entries\":[],\"errors\":[]}}\n",
+                sw_install::json_string(dir)
+            )
+        };
+        assert_eq!(stdout, expected, "{extra:?}");
+    }
+}
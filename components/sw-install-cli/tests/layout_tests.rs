@@ -0,0 +1,155 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+//! Tests for flat and nested install layouts.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use sw_install::{
+    InstallConfig, InstallLayout, Installer, ListFormat, Lister, NormalOutput, SortOrder,
+    Uninstaller, json_string,
+};
+use tempfile::TempDir;
+
+fn install(project: &Path, bin_dir: &Path, name: &str, layout: InstallLayout) -> PathBuf {
+    let target_dir = project.join("target").join("release");
+    fs::create_dir_all(&target_dir).unwrap();
+    let source = target_dir.join(name);
+    fs::write(&source, format!("binary {name}")).unwrap();
+    let mut config = InstallConfig::new(
+        project.to_path_buf(),
+        None,
+        vec![],
+        false,
+        false,
+        false,
+        false,
+        Some(bin_dir.to_path_buf()),
+    );
+    config.layout = layout;
+    let output = NormalOutput::default();
+    Installer::new(&config, name.to_string(), source, &output)
+        .install()
+        .unwrap()
+}
+
+fn listed_names(bin_dir: &Path) -> Vec<String> {
+    let output = NormalOutput::default();
+    Lister::new(Some(bin_dir.to_path_buf()), SortOrder::Name, &output)
+        .collect()
+        .unwrap()
+        .into_iter()
+        .map(|b| b.name)
+        .collect()
+}
+
+#[test]
+fn test_nested_layout_installs_into_subdir() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+
+    let dest = install(project.path(), bin_dir.path(), "ask", InstallLayout::Nested);
+    assert_eq!(dest, bin_dir.path().join("ask").join("ask"));
+    assert_eq!(fs::read_to_string(dest).unwrap(), "binary ask");
+}
+
+#[test]
+fn test_list_understands_both_layouts() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    install(
+        project.path(),
+        bin_dir.path(),
+        "flat-tool",
+        InstallLayout::Flat,
+    );
+    install(
+        project.path(),
+        bin_dir.path(),
+        "nested-tool",
+        InstallLayout::Nested,
+    );
+    fs::create_dir_all(bin_dir.path().join("not-a-tool")).unwrap();
+
+    assert_eq!(
+        listed_names(bin_dir.path()),
+        vec!["flat-tool".to_string(), "nested-tool".to_string()]
+    );
+}
+
+#[test]
+fn test_json_path_points_at_installed_file_in_either_layout() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    let flat = install(
+        project.path(),
+        bin_dir.path(),
+        "flat-tool",
+        InstallLayout::Flat,
+    );
+    let nested = install(
+        project.path(),
+        bin_dir.path(),
+        "nested-tool",
+        InstallLayout::Nested,
+    );
+
+    let output = NormalOutput::default();
+    let lister = Lister::new(Some(bin_dir.path().to_path_buf()), SortOrder::Name, &output)
+        .with_format(ListFormat::Json);
+    let json = lister.render(&lister.scan().unwrap());
+    for dest in [&flat, &nested] {
+        assert!(dest.is_absolute() && dest.is_file());
+        let path = json_string(&dest.display().to_string());
+        assert!(json.contains(&format!("\"path\":{path},")), "{json}");
+    }
+}
+
+#[test]
+fn test_uninstall_removes_nested_binary_and_subdir() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    install(project.path(), bin_dir.path(), "ask", InstallLayout::Nested);
+
+    let output = NormalOutput::default();
+    Uninstaller::new(
+        "ask".to_string(),
+        false,
+        Some(bin_dir.path().to_path_buf()),
+        &output,
+    )
+    .uninstall()
+    .unwrap();
+
+    assert!(!bin_dir.path().join("ask").exists());
+    assert!(listed_names(bin_dir.path()).is_empty());
+}
+
+#[test]
+fn test_layout_from_str() {
+    assert_eq!(
+        "flat".parse::<InstallLayout>().unwrap(),
+        InstallLayout::Flat
+    );
+    assert_eq!(
+        "Nested".parse::<InstallLayout>().unwrap(),
+        InstallLayout::Nested
+    );
+    assert!("tree".parse::<InstallLayout>().is_err());
+}
+
+#[test]
+fn test_list_follows_subdirectories_one_level() {
+    let bin_dir = TempDir::new().unwrap();
+    fs::create_dir_all(bin_dir.path().join("ask")).unwrap();
+    fs::write(bin_dir.path().join("ask").join("ask"), "nested").unwrap();
+    fs::write(bin_dir.path().join("chat"), "flat").unwrap();
+    let deep = bin_dir.path().join("deep").join("deep");
+    fs::create_dir_all(&deep).unwrap();
+    fs::write(deep.join("deep"), "two levels down").unwrap();
+
+    assert_eq!(
+        listed_names(bin_dir.path()),
+        vec!["ask".to_string(), "chat".to_string()]
+    );
+}
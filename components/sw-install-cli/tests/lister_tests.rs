@@ -3,10 +3,16 @@
 
 //! Tests for the Lister module.
 
+mod common;
+
+use common::run;
 use serial_test::serial;
 use std::fs;
-use std::time::SystemTime;
-use sw_install::{InstallError, Lister, NormalOutput, SortOrder, format_time_ago};
+use std::time::{Duration, SystemTime};
+use sw_install::{
+    CLEAR_SCREEN, InstallDate, InstallError, ListOptions, Lister, Listing, ManifestEntry,
+    ManifestStore, MemoryManifestStore, NamePattern, NormalOutput, SortOrder, format_time_ago,
+};
 use tempfile::TempDir;
 
 #[test]
@@ -265,3 +271,387 @@ fn test_sort_order_from_str() {
     assert_eq!("newest".parse::<SortOrder>().unwrap(), SortOrder::Newest);
     assert!("invalid".parse::<SortOrder>().is_err());
 }
+
+#[test]
+#[serial]
+fn test_list_ignores_lock_file() {
+    let temp_home = TempDir::new().unwrap();
+    let test_bin_dir = temp_home.path().join("bin");
+    fs::create_dir_all(&test_bin_dir).unwrap();
+    fs::write(test_bin_dir.join("app"), "fake binary").unwrap();
+    fs::write(test_bin_dir.join(".sw-install.lock"), "").unwrap();
+
+    let output = NormalOutput::default();
+    let lister = Lister::new(Some(test_bin_dir.clone()), SortOrder::Name, &output);
+
+    assert_eq!(lister.list().unwrap(), vec!["app".to_string()]);
+}
+
+fn render_names_only(bin_dir: &std::path::Path) -> String {
+    let output = NormalOutput::default();
+    let options = ListOptions {
+        names_only: true,
+        ..ListOptions::default()
+    };
+    let lister =
+        Lister::new(Some(bin_dir.to_path_buf()), SortOrder::Name, &output).with_options(options);
+    lister.render(&lister.scan().unwrap())
+}
+
+#[test]
+fn test_names_only_prints_bare_names() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("beta"), "fake").unwrap();
+    fs::write(temp_dir.path().join("alpha"), "fake").unwrap();
+
+    assert_eq!(render_names_only(temp_dir.path()), "alpha\nbeta\n");
+}
+
+#[test]
+fn test_names_only_empty_dir_prints_nothing() {
+    let temp_dir = TempDir::new().unwrap();
+    assert_eq!(render_names_only(temp_dir.path()), "");
+}
+
+fn grep_names(bin_dir: &std::path::Path, pattern: &str) -> Vec<String> {
+    let output = NormalOutput::default();
+    Lister::new(Some(bin_dir.to_path_buf()), SortOrder::Name, &output)
+        .with_grep(Some(pattern.parse::<NamePattern>().unwrap()))
+        .collect()
+        .unwrap()
+        .into_iter()
+        .map(|b| b.name)
+        .collect()
+}
+
+#[test]
+fn test_grep_filters_with_anchored_pattern() {
+    let temp_dir = TempDir::new().unwrap();
+    for name in ["sw-install", "sw-checklist", "my-sw-tool", "ask2"] {
+        fs::write(temp_dir.path().join(name), "fake").unwrap();
+    }
+
+    assert_eq!(
+        grep_names(temp_dir.path(), "^sw-"),
+        vec!["sw-checklist".to_string(), "sw-install".to_string()]
+    );
+    assert_eq!(grep_names(temp_dir.path(), "tool$|\\d$").len(), 2);
+    assert_eq!(grep_names(temp_dir.path(), "^[a-z]+-[a-z]+$").len(), 2);
+}
+
+#[test]
+fn test_grep_invalid_pattern_is_an_error() {
+    for pattern in ["[abc", "(sw|my)", "*x", "a\\"] {
+        let err = pattern.parse::<NamePattern>().unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with(&format!("Invalid pattern '{pattern}'"))
+        );
+        assert!(err.to_string().contains("Hint: --grep supports only"));
+    }
+}
+
+#[test]
+fn test_grep_nested_repetition_does_not_backtrack_exponentially() {
+    let pattern: NamePattern = "^a*a*a*a*a*a*a*a*a*a*a*a*b$".parse().unwrap();
+    let name = "a".repeat(200);
+    assert!(!pattern.is_match(&name));
+    assert!(pattern.is_match(&format!("{name}b")));
+}
+
+#[test]
+fn test_quiet_empty_dir_prints_nothing() {
+    let temp_dir = TempDir::new().unwrap();
+    let output = NormalOutput::default();
+    let quiet = ListOptions {
+        quiet: true,
+        ..ListOptions::default()
+    };
+    let lister = Lister::new(
+        Some(temp_dir.path().to_path_buf()),
+        SortOrder::Name,
+        &output,
+    );
+    assert_eq!(
+        lister.render(&Listing::default()),
+        "No binaries installed\n"
+    );
+    assert_eq!(lister.with_options(quiet).render(&Listing::default()), "");
+}
+
+#[test]
+fn test_equal_mtimes_tiebreak_by_name() {
+    let temp_dir = TempDir::new().unwrap();
+    let stamp = std::time::SystemTime::now() - std::time::Duration::from_secs(600);
+    for name in ["gamma", "alpha", "beta"] {
+        let path = temp_dir.path().join(name);
+        fs::write(&path, "fake binary").unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(stamp)
+            .unwrap();
+    }
+
+    let output = NormalOutput::default();
+    for order in [SortOrder::Oldest, SortOrder::Newest] {
+        let lister = Lister::new(Some(temp_dir.path().to_path_buf()), order, &output);
+        let names: Vec<String> = lister
+            .collect()
+            .unwrap()
+            .into_iter()
+            .map(|b| b.name)
+            .collect();
+        assert_eq!(names, vec!["alpha", "beta", "gamma"]);
+    }
+}
+
+#[test]
+fn test_dedupe_by_checksum_groups_identical_binaries() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    let target_dir = project.path().join("target").join("release");
+    fs::create_dir_all(&target_dir).unwrap();
+    let source = target_dir.join("ask");
+    fs::write(&source, "same bytes").unwrap();
+    fs::write(bin_dir.path().join("other"), "different bytes").unwrap();
+
+    let output = NormalOutput::default();
+    for rename in [None, Some("ask-dev".to_string())] {
+        let config = sw_install::InstallConfig::new(
+            project.path().to_path_buf(),
+            rename,
+            vec![],
+            false,
+            false,
+            false,
+            false,
+            Some(bin_dir.path().to_path_buf()),
+        );
+        sw_install::Installer::new(&config, "ask".to_string(), source.clone(), &output)
+            .install()
+            .unwrap();
+    }
+
+    let options = ListOptions {
+        dedupe: true,
+        ..ListOptions::default()
+    };
+    let lister = Lister::new(Some(bin_dir.path().to_path_buf()), SortOrder::Name, &output)
+        .with_options(options);
+    let listing = lister.scan().unwrap();
+    let bins = &listing.binaries;
+    let names: Vec<&str> = bins.iter().map(|b| b.name.as_str()).collect();
+    assert_eq!(names, vec!["ask", "other"]);
+    assert_eq!(bins[0].aliases, vec!["ask-dev".to_string()]);
+    assert!(bins[1].aliases.is_empty());
+    let text = lister.render(&listing);
+    assert!(text.contains("ask ("));
+    assert!(text.contains("[also: ask-dev]"));
+}
+
+fn write_aged(dir: &std::path::Path, name: &str, days: u64) {
+    let path = dir.join(name);
+    fs::write(&path, "fake binary").unwrap();
+    let age = std::time::Duration::from_secs(days * 24 * 60 * 60);
+    fs::File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(SystemTime::now() - age)
+        .unwrap();
+}
+
+#[test]
+fn test_stale_days_keeps_only_older_binaries() {
+    let temp_dir = TempDir::new().unwrap();
+    write_aged(temp_dir.path(), "fresh", 0);
+    write_aged(temp_dir.path(), "recent", 10);
+    write_aged(temp_dir.path(), "old", 45);
+    write_aged(temp_dir.path(), "ancient", 400);
+
+    let output = NormalOutput::default();
+    let stale = |days| {
+        Lister::new(
+            Some(temp_dir.path().to_path_buf()),
+            SortOrder::Oldest,
+            &output,
+        )
+        .with_stale_days(Some(days))
+        .collect()
+        .unwrap()
+        .into_iter()
+        .map(|b| b.name)
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(stale(30), vec!["ancient", "old"]);
+    assert_eq!(stale(5), vec!["ancient", "old", "recent"]);
+    assert!(stale(1000).is_empty());
+}
+
+#[test]
+fn test_with_dir_lists_arbitrary_directory() {
+    let install_dir = TempDir::new().unwrap();
+    fs::write(install_dir.path().join("installed"), "fake binary").unwrap();
+    let cargo_bin = TempDir::new().unwrap();
+    for name in ["rg", "fd", "cargo-watch"] {
+        fs::write(cargo_bin.path().join(name), "fake binary").unwrap();
+    }
+
+    let output = NormalOutput::default();
+    let lister = Lister::new(
+        Some(install_dir.path().to_path_buf()),
+        SortOrder::Name,
+        &output,
+    );
+    let names = lister
+        .with_dir(Some(cargo_bin.path().to_path_buf()))
+        .list()
+        .unwrap();
+    assert_eq!(names, vec!["cargo-watch", "fd", "rg"]);
+
+    let lister = Lister::new(
+        Some(install_dir.path().to_path_buf()),
+        SortOrder::Name,
+        &output,
+    );
+    assert_eq!(lister.list().unwrap(), vec!["installed"]);
+}
+
+#[test]
+fn test_with_dir_missing_directory_fails_without_setup_hint() {
+    let temp_dir = TempDir::new().unwrap();
+    let missing = temp_dir.path().join("missing");
+
+    let output = NormalOutput::default();
+    let error = Lister::new(None, SortOrder::Name, &output)
+        .with_dir(Some(missing.clone()))
+        .list()
+        .unwrap_err();
+    assert!(matches!(error, InstallError::ListDirNotFound(ref dir) if *dir == missing));
+    assert!(!error.to_string().contains("--setup-install-dir"));
+}
+
+#[test]
+fn test_list_returns_names_in_collected_order() {
+    let temp_home = TempDir::new().unwrap();
+    let test_bin_dir = temp_home.path().join("bin");
+    fs::create_dir_all(&test_bin_dir).unwrap();
+    let base = SystemTime::now() - std::time::Duration::from_secs(600);
+    for (i, name) in ["delta", "alpha", "charlie", "bravo"].iter().enumerate() {
+        let path = test_bin_dir.join(name);
+        fs::write(&path, format!("binary {name}")).unwrap();
+        let mtime = base + std::time::Duration::from_secs(i as u64 * 60);
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(mtime)
+            .unwrap();
+    }
+
+    let output = NormalOutput::default();
+    let lister = Lister::new(Some(test_bin_dir), SortOrder::Newest, &output);
+    let collected: Vec<String> = lister
+        .collect()
+        .unwrap()
+        .into_iter()
+        .map(|b| b.name)
+        .collect();
+    assert_eq!(collected, vec!["bravo", "charlie", "alpha", "delta"]);
+    assert_eq!(lister.list().unwrap(), collected);
+}
+
+#[test]
+fn test_install_date_parses_dates_and_timestamps() {
+    let day = |days: u64| SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(days * 86_400);
+    let june_first = "2025-06-01".parse::<InstallDate>().unwrap();
+    assert_eq!(june_first.0, day(20_240));
+    let noon = "2025-06-01T14:00:00+02:00".parse::<InstallDate>().unwrap();
+    assert_eq!(
+        noon.0,
+        day(20_240) + std::time::Duration::from_secs(12 * 3600)
+    );
+    let error = "06/01/2025".parse::<InstallDate>().unwrap_err();
+    assert!(error.to_string().contains("Use YYYY-MM-DD"));
+}
+
+#[test]
+fn test_installed_range_filters_by_manifest_time_or_mtime() {
+    let temp_home = TempDir::new().unwrap();
+    let bin_dir = temp_home.path().join("bin");
+    fs::create_dir_all(&bin_dir).unwrap();
+    let at = |date: &str| date.parse::<InstallDate>().unwrap().0;
+    let store = MemoryManifestStore::default();
+    for (name, date) in [("early", "2025-05-20"), ("during", "2025-06-03")] {
+        fs::write(bin_dir.join(name), "fake binary").unwrap();
+        store
+            .record(ManifestEntry::new(name, None, at(date)))
+            .unwrap();
+    }
+    // Untracked, so its mtime stands in for the install time
+    let untracked = bin_dir.join("untracked");
+    fs::write(&untracked, "fake binary").unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&untracked)
+        .unwrap()
+        .set_modified(at("2025-06-07T23:59:59Z"))
+        .unwrap();
+
+    let output = NormalOutput::default();
+    let names = |after: Option<&str>, before: Option<&str>| {
+        Lister::new(Some(bin_dir.clone()), SortOrder::Name, &output)
+            .with_manifest_store(&store)
+            .with_installed_range(
+                after.map(|d| d.parse().unwrap()),
+                before.map(|d| d.parse().unwrap()),
+            )
+            .list()
+            .unwrap()
+    };
+    assert_eq!(
+        names(Some("2025-06-01"), Some("2025-06-08")),
+        vec!["during", "untracked"]
+    );
+    assert_eq!(names(None, Some("2025-06-03")), vec!["early"]);
+    assert_eq!(names(Some("2025-06-03"), None), vec!["during", "untracked"]);
+}
+
+#[test]
+fn test_watch_refresh_draws_one_cleared_frame() {
+    let temp = TempDir::new().unwrap();
+    let bin_dir = temp.path().join("bin");
+    fs::create_dir_all(&bin_dir).unwrap();
+    fs::write(bin_dir.join("tool-a"), "a").unwrap();
+    fs::write(bin_dir.join("tool-b"), "b").unwrap();
+
+    let output = NormalOutput::default();
+    let lister = Lister::new(Some(bin_dir), SortOrder::Name, &output);
+    let mut frame = Vec::new();
+    let count = lister.refresh(&mut frame, Duration::from_secs(3)).unwrap();
+    let frame = String::from_utf8(frame).unwrap();
+
+    assert_eq!(count, 2);
+    assert!(frame.starts_with(CLEAR_SCREEN));
+    assert_eq!(frame.matches(CLEAR_SCREEN).count(), 1);
+    assert!(frame.contains("Every 3s"));
+    assert!(frame.find("tool-a").unwrap() < frame.find("tool-b").unwrap());
+}
+
+#[test]
+fn test_list_exit_code_count_reflects_installed_binaries() {
+    let bin_dir = TempDir::new().unwrap();
+    let exit_code = || {
+        let dir = bin_dir.path().to_str().unwrap();
+        let output = run(&["--list", "--exit-code-count", "-q", "--test-dir", dir]);
+        output.status.code()
+    };
+    assert_eq!(exit_code(), Some(0));
+    fs::write(bin_dir.path().join("one"), "fake binary").unwrap();
+    assert_eq!(exit_code(), Some(1));
+    fs::write(bin_dir.path().join("two"), "fake binary").unwrap();
+    fs::write(bin_dir.path().join("three"), "fake binary").unwrap();
+    assert_eq!(exit_code(), Some(3));
+}
@@ -0,0 +1,527 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+//! Tests for the install manifest and manifest repair.
+
+mod common;
+
+use common::{create_two_tool_workspace, install_fake, run};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use sw_install::{
+    CORRUPT_SUFFIX, CapturedOutput, FileManifestStore, InstallConfig, InstallError, Installer,
+    ListFormat, ListOptions, Lister, MANIFEST_FILE_NAME, Manifest, ManifestEntry, ManifestRepair,
+    ManifestStore, MemoryManifestStore, NormalOutput, PruneReason, SortOrder, Uninstaller,
+};
+use tempfile::TempDir;
+
+#[test]
+fn test_install_records_manifest_entry() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    install_fake(project.path(), bin_dir.path(), "app");
+
+    assert!(bin_dir.path().join(MANIFEST_FILE_NAME).exists());
+    let manifest = Manifest::load(bin_dir.path(), &NormalOutput::default()).unwrap();
+    let entry = manifest.get("app").unwrap();
+    assert_eq!(
+        entry.source.as_deref(),
+        Some(fs::canonicalize(project.path()).unwrap().as_path())
+    );
+    assert!(entry.installed_at > 0);
+}
+
+#[test]
+fn test_rename_onto_binary_from_another_project_is_refused() {
+    let first = TempDir::new().unwrap();
+    let second = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    install_fake(first.path(), bin_dir.path(), "tool");
+    let target_dir = second.path().join("target").join("release");
+    fs::create_dir_all(&target_dir).unwrap();
+    fs::write(target_dir.join("other"), "binary other").unwrap();
+    let install = |rename: Option<&str>, force: bool| {
+        let config = InstallConfig::builder(second.path())
+            .rename(rename.map(String::from))
+            .test_dir(Some(bin_dir.path().to_path_buf()))
+            .force(force)
+            .build();
+        let output = NormalOutput::default();
+        Installer::new(
+            &config,
+            "other".to_string(),
+            target_dir.join("other"),
+            &output,
+        )
+        .install()
+    };
+
+    let error = install(Some("tool"), false).unwrap_err();
+    let owner = fs::canonicalize(first.path()).unwrap();
+    assert!(matches!(error, InstallError::NameTaken(ref n, ref p) if n == "tool" && *p == owner));
+    assert_eq!(
+        fs::read_to_string(bin_dir.path().join("tool")).unwrap(),
+        "binary tool"
+    );
+
+    install(None, false).unwrap();
+    install(Some("tool"), true).unwrap();
+    assert_eq!(
+        fs::read_to_string(bin_dir.path().join("tool")).unwrap(),
+        "binary other"
+    );
+}
+
+#[test]
+fn test_uninstall_removes_manifest_entry() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    install_fake(project.path(), bin_dir.path(), "app");
+    install_fake(project.path(), bin_dir.path(), "other");
+
+    let output = NormalOutput::default();
+    Uninstaller::new(
+        "app".to_string(),
+        false,
+        Some(bin_dir.path().to_path_buf()),
+        &output,
+    )
+    .uninstall()
+    .unwrap();
+
+    let manifest = Manifest::load(bin_dir.path(), &NormalOutput::default()).unwrap();
+    assert!(manifest.get("app").is_none());
+    assert!(manifest.get("other").is_some());
+}
+
+#[test]
+fn test_manifest_round_trips_entries() {
+    let dir = TempDir::new().unwrap();
+    let mut manifest = Manifest::default();
+    let mut entry = ManifestEntry::new("tool", Some(PathBuf::from("/src/tool")), SystemTime::now());
+    entry.target_triple = Some("aarch64-unknown-linux-gnu".to_string());
+    manifest.record(entry.clone());
+    manifest.record(ManifestEntry::new("bare", None, SystemTime::UNIX_EPOCH));
+    manifest.save(dir.path()).unwrap();
+
+    let loaded = Manifest::load(dir.path(), &NormalOutput::default()).unwrap();
+    assert_eq!(loaded.get("tool"), Some(&entry));
+    assert_eq!(loaded.get("bare").unwrap().source, None);
+}
+
+#[test]
+fn test_repair_manifest_reconciles_with_disk() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    install_fake(project.path(), bin_dir.path(), "tracked");
+    fs::write(bin_dir.path().join("manual"), "copied by hand").unwrap();
+    fs::write(bin_dir.path().join("tracked.bak-1700000000000"), "old").unwrap();
+    let mut manifest = Manifest::load(bin_dir.path(), &NormalOutput::default()).unwrap();
+    manifest.record(ManifestEntry::new(
+        "deleted",
+        Some(PathBuf::from("/gone")),
+        SystemTime::now(),
+    ));
+    manifest.save(bin_dir.path()).unwrap();
+
+    let output = NormalOutput::default();
+    let report = ManifestRepair::new(false, Some(bin_dir.path().to_path_buf()), &output)
+        .repair()
+        .unwrap();
+    assert_eq!(report.kept, vec!["tracked".to_string()]);
+    assert_eq!(report.added, vec!["manual".to_string()]);
+    assert_eq!(report.dropped, vec!["deleted".to_string()]);
+
+    let repaired = Manifest::load(bin_dir.path(), &NormalOutput::default()).unwrap();
+    assert!(repaired.get("deleted").is_none());
+    assert_eq!(repaired.get("manual").unwrap().source, None);
+    assert!(repaired.get("tracked").unwrap().source.is_some());
+}
+
+/// One valid install, one whose project was deleted, and one dangling link.
+fn create_prune_mix(bin_dir: &Path, kept: &Path) -> PathBuf {
+    let gone = TempDir::new().unwrap();
+    install_fake(kept, bin_dir, "valid");
+    install_fake(gone.path(), bin_dir, "orphan");
+    let gone_path = gone.path().to_path_buf();
+    drop(gone);
+    std::os::unix::fs::symlink(bin_dir.join("missing-target"), bin_dir.join("dangling")).unwrap();
+    gone_path
+}
+
+#[test]
+fn test_prune_removes_orphaned_and_dangling_entries() {
+    let bin_dir = TempDir::new().unwrap();
+    let kept = TempDir::new().unwrap();
+    let gone = create_prune_mix(bin_dir.path(), kept.path());
+
+    let output = NormalOutput::default();
+    let mut asked = Vec::new();
+    let pruned = ManifestRepair::new(false, Some(bin_dir.path().to_path_buf()), &output)
+        .prune(|entries| {
+            asked = entries.iter().map(|e| e.name.clone()).collect();
+            true
+        })
+        .unwrap();
+    assert_eq!(asked, vec!["orphan".to_string(), "dangling".to_string()]);
+    assert_eq!(pruned[0].reason, PruneReason::SourceGone(gone));
+    assert_eq!(pruned[1].reason, PruneReason::DanglingLink);
+
+    assert!(bin_dir.path().join("valid").exists());
+    assert!(!bin_dir.path().join("orphan").exists());
+    assert!(fs::symlink_metadata(bin_dir.path().join("dangling")).is_err());
+    let manifest = Manifest::load(bin_dir.path(), &NormalOutput::default()).unwrap();
+    assert!(manifest.get("valid").is_some());
+    assert!(manifest.get("orphan").is_none());
+}
+
+#[test]
+fn test_prune_dry_run_and_declined_prompt_keep_everything() {
+    let bin_dir = TempDir::new().unwrap();
+    let kept = TempDir::new().unwrap();
+    create_prune_mix(bin_dir.path(), kept.path());
+    let output = NormalOutput::default();
+
+    let dry = ManifestRepair::new(true, Some(bin_dir.path().to_path_buf()), &output)
+        .prune(|_| panic!("dry run must not prompt"))
+        .unwrap();
+    assert_eq!(dry.len(), 2);
+    let declined = ManifestRepair::new(false, Some(bin_dir.path().to_path_buf()), &output)
+        .prune(|_| false)
+        .unwrap();
+    assert!(declined.is_empty());
+
+    assert!(bin_dir.path().join("orphan").exists());
+    assert!(fs::symlink_metadata(bin_dir.path().join("dangling")).is_ok());
+    assert!(
+        Manifest::load(bin_dir.path(), &NormalOutput::default())
+            .unwrap()
+            .get("orphan")
+            .is_some()
+    );
+}
+
+#[test]
+fn test_repair_manifest_dry_run_does_not_write() {
+    let bin_dir = TempDir::new().unwrap();
+    fs::write(bin_dir.path().join("manual"), "copied by hand").unwrap();
+
+    let output = NormalOutput::default();
+    let report = ManifestRepair::new(true, Some(bin_dir.path().to_path_buf()), &output)
+        .repair()
+        .unwrap();
+    assert_eq!(report.added, vec!["manual".to_string()]);
+    assert!(!bin_dir.path().join(MANIFEST_FILE_NAME).exists());
+}
+
+#[test]
+fn test_list_long_marks_debug_builds() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    install_fake(project.path(), bin_dir.path(), "release-app");
+
+    let source_path = project
+        .path()
+        .join("target")
+        .join("debug")
+        .join("debug-app");
+    fs::create_dir_all(source_path.parent().unwrap()).unwrap();
+    fs::write(&source_path, "debug binary").unwrap();
+    let config = InstallConfig::new(
+        project.path().to_path_buf(),
+        None,
+        vec![],
+        true,
+        false,
+        false,
+        false,
+        Some(bin_dir.path().to_path_buf()),
+    );
+    let output = NormalOutput::default();
+    Installer::new(&config, "debug-app".to_string(), source_path, &output)
+        .install()
+        .unwrap();
+    assert!(
+        Manifest::load(bin_dir.path(), &NormalOutput::default())
+            .unwrap()
+            .get("debug-app")
+            .unwrap()
+            .debug
+    );
+
+    let options = ListOptions {
+        long: true,
+        ..ListOptions::default()
+    };
+    let lister = Lister::new(Some(bin_dir.path().to_path_buf()), SortOrder::Name, &output)
+        .with_options(options);
+    let text = lister.render(&lister.scan().unwrap());
+    let line = |name: &str| {
+        text.lines()
+            .find(|l| l.starts_with(name))
+            .unwrap()
+            .to_string()
+    };
+    assert!(line("debug-app").ends_with("(debug)"));
+    assert!(!line("release-app").contains("(debug)"));
+}
+
+#[test]
+fn test_list_json_reports_recorded_build_type() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    install_fake(project.path(), bin_dir.path(), "release-app");
+    let source_path = project
+        .path()
+        .join("target")
+        .join("debug")
+        .join("debug-app");
+    fs::create_dir_all(source_path.parent().unwrap()).unwrap();
+    fs::write(&source_path, "debug binary").unwrap();
+    let config = InstallConfig::builder(project.path())
+        .use_debug(true)
+        .install_dir(Some(bin_dir.path().to_path_buf()))
+        .build();
+    let output = NormalOutput::default();
+    Installer::new(&config, "debug-app".to_string(), source_path, &output)
+        .install()
+        .unwrap();
+    fs::write(bin_dir.path().join("untracked"), "copied by hand").unwrap();
+
+    let lister = Lister::new(Some(bin_dir.path().to_path_buf()), SortOrder::Name, &output)
+        .with_format(ListFormat::Json);
+    let json = lister.render(&lister.scan().unwrap());
+    let entry = |name: &str| {
+        let start = json.find(&format!("{{\"name\":\"{name}\"")).unwrap();
+        json[start..start + json[start..].find('}').unwrap()].to_string()
+    };
+    assert!(entry("debug-app").contains("\"build_type\":\"debug\""));
+    assert!(entry("release-app").contains("\"build_type\":\"release\""));
+    assert!(entry("untracked").contains("\"build_type\":null"));
+    assert!(entry("debug-app").contains("\"target_triple\":null"));
+}
+
+#[test]
+fn test_list_groups_binaries_by_source() {
+    let first = TempDir::new().unwrap();
+    let second = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    install_fake(first.path(), bin_dir.path(), "alpha");
+    install_fake(first.path(), bin_dir.path(), "beta");
+    install_fake(second.path(), bin_dir.path(), "gamma");
+    fs::write(bin_dir.path().join("manual"), "copied by hand").unwrap();
+
+    let output = NormalOutput::default();
+    let options = ListOptions {
+        group_by_source: true,
+        ..ListOptions::default()
+    };
+    let lister = Lister::new(Some(bin_dir.path().to_path_buf()), SortOrder::Name, &output)
+        .with_options(options);
+    let text = lister.render(&lister.scan().unwrap());
+    let headings: Vec<&str> = text.lines().filter(|l| l.ends_with(':')).collect();
+
+    let heading = |dir: &TempDir| format!("{}:", fs::canonicalize(dir.path()).unwrap().display());
+    let mut expected = vec![heading(&first), heading(&second)];
+    expected.sort();
+    expected.push("(unknown source):".to_string());
+    assert_eq!(headings, expected);
+    let first_block = text.split(&heading(&first)).nth(1).unwrap();
+    let first_block = first_block.split("\n\n").next().unwrap();
+    assert!(first_block.contains("alpha (") && first_block.contains("beta ("));
+    assert!(!first_block.contains("gamma"));
+    let unknown = text.split("(unknown source):\n").nth(1).unwrap();
+    assert!(unknown.starts_with("manual ("));
+}
+
+#[test]
+fn test_memory_store_tracks_install_and_uninstall() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    let target_dir = project.path().join("target").join("release");
+    fs::create_dir_all(&target_dir).unwrap();
+    fs::write(target_dir.join("app"), "binary app").unwrap();
+    let config = InstallConfig::new(
+        project.path().to_path_buf(),
+        None,
+        vec![],
+        true,
+        false,
+        false,
+        false,
+        Some(bin_dir.path().to_path_buf()),
+    );
+    let output = NormalOutput::default();
+    let store = MemoryManifestStore::default();
+
+    Installer::new(&config, "app".to_string(), target_dir.join("app"), &output)
+        .with_manifest_store(&store)
+        .install()
+        .unwrap();
+    let entries = store.all().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].name, "app");
+    assert!(entries[0].debug);
+    assert!(!bin_dir.path().join(MANIFEST_FILE_NAME).exists());
+
+    let lister = Lister::new(Some(bin_dir.path().to_path_buf()), SortOrder::Name, &output)
+        .with_manifest_store(&store);
+    assert!(lister.collect().unwrap()[0].debug);
+
+    Uninstaller::new(
+        "app".to_string(),
+        false,
+        Some(bin_dir.path().to_path_buf()),
+        &output,
+    )
+    .with_manifest_store(&store)
+    .uninstall()
+    .unwrap();
+    assert!(store.all().unwrap().is_empty());
+}
+
+#[test]
+fn test_file_store_matches_manifest_file() {
+    let bin_dir = TempDir::new().unwrap();
+    let output = NormalOutput::default();
+    let store = FileManifestStore::new(bin_dir.path(), &output);
+    store
+        .record(ManifestEntry::new("app", None, SystemTime::now()))
+        .unwrap();
+
+    assert!(
+        Manifest::load(bin_dir.path(), &NormalOutput::default())
+            .unwrap()
+            .get("app")
+            .is_some()
+    );
+    assert_eq!(store.remove("app").unwrap().unwrap().name, "app");
+    assert_eq!(store.remove("app").unwrap(), None);
+    assert!(store.load().unwrap().entries().next().is_none());
+}
+
+#[test]
+fn test_manifest_file_is_sorted_by_name() {
+    let dir = TempDir::new().unwrap();
+    let mut manifest = Manifest::default();
+    for name in ["zeta", "alpha", "mid"] {
+        manifest.record(ManifestEntry::new(name, None, SystemTime::UNIX_EPOCH));
+    }
+    manifest.save(dir.path()).unwrap();
+    let first = fs::read_to_string(dir.path().join(MANIFEST_FILE_NAME)).unwrap();
+
+    let headers: Vec<&str> = first
+        .lines()
+        .filter(|l| l.starts_with("[binaries."))
+        .collect();
+    assert_eq!(
+        headers,
+        ["[binaries.alpha]", "[binaries.mid]", "[binaries.zeta]"]
+    );
+
+    let mut reordered = Manifest::default();
+    for name in ["mid", "zeta", "alpha"] {
+        reordered.record(ManifestEntry::new(name, None, SystemTime::UNIX_EPOCH));
+    }
+    reordered.save(dir.path()).unwrap();
+    let second = fs::read_to_string(dir.path().join(MANIFEST_FILE_NAME)).unwrap();
+    assert_eq!(first, second);
+}
+
+#[test]
+fn test_corrupt_manifest_is_set_aside_and_operations_proceed() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    let garbage = "[binaries\nthis is = = not toml";
+    let manifest_path = bin_dir.path().join(MANIFEST_FILE_NAME);
+    fs::write(&manifest_path, garbage).unwrap();
+
+    install_fake(project.path(), bin_dir.path(), "app");
+    let corrupt = bin_dir
+        .path()
+        .join(format!("{MANIFEST_FILE_NAME}{CORRUPT_SUFFIX}"));
+    assert_eq!(fs::read_to_string(&corrupt).unwrap(), garbage);
+    let manifest = Manifest::load(bin_dir.path(), &NormalOutput::default()).unwrap();
+    assert!(manifest.get("app").unwrap().source.is_some());
+
+    // A listing and an uninstall after a fresh corruption also proceed,
+    // warning through the caller's output
+    fs::write(&manifest_path, garbage).unwrap();
+    let captured = CapturedOutput::default();
+    let output = NormalOutput::default().with_handler(captured.clone());
+    let lister = Lister::new(Some(bin_dir.path().to_path_buf()), SortOrder::Name, &output);
+    assert_eq!(lister.list().unwrap(), vec!["app".to_string()]);
+    let warnings = captured.lines();
+    assert_eq!(warnings.len(), 1, "{warnings:?}");
+    assert!(warnings[0].starts_with("Warning: Install manifest "));
+    fs::write(&manifest_path, garbage).unwrap();
+    Uninstaller::new(
+        "app".to_string(),
+        false,
+        Some(bin_dir.path().to_path_buf()),
+        &output,
+    )
+    .uninstall()
+    .unwrap();
+    assert!(!bin_dir.path().join("app").exists());
+    assert_eq!(fs::read_to_string(&corrupt).unwrap(), garbage);
+}
+
+#[test]
+fn test_unreadable_manifest_fails_instead_of_starting_empty() {
+    let bin_dir = TempDir::new().unwrap();
+    // A directory in the manifest's place cannot be read as a file
+    fs::create_dir(bin_dir.path().join(MANIFEST_FILE_NAME)).unwrap();
+
+    let result = Manifest::load(bin_dir.path(), &NormalOutput::default());
+    assert!(matches!(
+        result,
+        Err(InstallError::IoContext {
+            step: "reading",
+            ..
+        })
+    ));
+    let corrupt = bin_dir
+        .path()
+        .join(format!("{MANIFEST_FILE_NAME}{CORRUPT_SUFFIX}"));
+    assert!(!corrupt.exists());
+
+    // Installing and listing report the error rather than dropping the
+    // ownership check and recorded details
+    let project = TempDir::new().unwrap();
+    let source = project.path().join("app");
+    fs::write(&source, "binary app").unwrap();
+    let config = InstallConfig::builder(project.path())
+        .test_dir(Some(bin_dir.path().to_path_buf()))
+        .build();
+    let output = NormalOutput::default();
+    let install = Installer::new(&config, "app".to_string(), source, &output).install();
+    assert!(matches!(install, Err(InstallError::IoContext { .. })));
+    assert!(!bin_dir.path().join("app").exists());
+    let lister = Lister::new(Some(bin_dir.path().to_path_buf()), SortOrder::Name, &output);
+    assert!(lister.collect().is_err());
+}
+
+#[test]
+fn test_prune_yes_removes_binary_whose_project_is_gone() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    create_two_tool_workspace(project.path());
+    let bin = bin_dir.path().to_str().unwrap();
+    let output = run(&["-p", project.path().to_str().unwrap(), "-t", bin]);
+    assert!(output.status.success(), "{output:?}");
+    drop(project);
+
+    let declined = run(&["--prune", "-t", bin]);
+    assert!(declined.status.success(), "{declined:?}");
+    assert!(String::from_utf8_lossy(&declined.stderr).contains("Remove these 2 entries? [y/N]"));
+    assert!(bin_dir.path().join("tool-a").exists());
+
+    let output = run(&["--prune", "--yes", "-t", bin]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Pruned 2 entries: 2 with a missing source, 0 dangling symlinks"));
+    assert!(!bin_dir.path().join("tool-a").exists());
+    assert!(!bin_dir.path().join("tool-b").exists());
+}
@@ -1,9 +1,14 @@
 // Copyright (c) 2025 Michael A Wright
 // Licensed under the MIT License
 
-//! Tests for the NormalOutput module.
+//! Tests for the NormalOutput module and the warnings and progress it
+//! reports.
 
+mod common;
+
+use common::{create_two_tool_workspace, run};
 use sw_install::NormalOutput;
+use tempfile::TempDir;
 
 #[test]
 fn test_create_normal_output() {
@@ -28,3 +33,58 @@ fn test_create_verbose_dry_run_output() {
     let output = NormalOutput::new(true, true);
     output.info("test");
 }
+
+#[test]
+fn test_fail_on_warning_exits_non_zero_after_installing() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    create_two_tool_workspace(project.path());
+    let project_arg = project.path().to_str().unwrap();
+    let bin_arg = bin_dir.path().to_str().unwrap();
+    let install = |extra: &[&str]| {
+        let mut args = vec!["-p", project_arg, "--bin", "tool-a", "--test-dir", bin_arg];
+        args.extend_from_slice(extra);
+        run(&args)
+    };
+
+    let output = install(&["-r", "test", "--fail-on-warning"]);
+    assert!(!output.status.success(), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Warning: 'test' is a shell builtin"));
+    assert!(stderr.contains("Error: 1 warning(s) emitted with --fail-on-warning"));
+    assert!(bin_dir.path().join("test").exists());
+
+    assert!(install(&["-r", "test"]).status.success());
+    assert!(install(&["--fail-on-warning"]).status.success());
+}
+
+#[test]
+fn test_progress_format_json_emits_step_events() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    create_two_tool_workspace(project.path());
+
+    let output = run(&[
+        "-p",
+        project.path().to_str().unwrap(),
+        "-t",
+        bin_dir.path().to_str().unwrap(),
+        "--bin",
+        "tool-a",
+        "--progress-format",
+        "json",
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    let events: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(String::from)
+        .collect();
+    let expected: Vec<String> = ["validate", "prepare", "copy", "permissions"]
+        .iter()
+        .flat_map(|step| {
+            ["start", "done"]
+                .map(|status| format!("{{\"step\":\"{step}\",\"status\":\"{status}\"}}"))
+        })
+        .collect();
+    assert_eq!(events, expected);
+}
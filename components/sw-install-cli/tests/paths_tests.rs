@@ -0,0 +1,184 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+//! Tests for installation directory resolution.
+
+mod common;
+
+use common::{create_two_tool_workspace, run, sw_install};
+use serial_test::serial;
+use std::fs;
+use std::path::{Path, PathBuf};
+use sw_install::{INSTALL_DIR_ENV, expand_env_vars, expand_tilde, resolve_install_dir};
+use tempfile::TempDir;
+
+#[test]
+#[serial]
+fn test_resolve_default_install_dir() {
+    unsafe { std::env::remove_var(INSTALL_DIR_ENV) };
+    let home = std::env::var("HOME").unwrap();
+    let dir = resolve_install_dir(None, None).unwrap();
+    assert_eq!(dir, PathBuf::from(home).join(".local/softwarewrighter/bin"));
+}
+
+#[test]
+#[serial]
+fn test_resolve_install_dir_from_env() {
+    unsafe { std::env::set_var(INSTALL_DIR_ENV, "/env/bin") };
+    let dir = resolve_install_dir(None, None);
+    unsafe { std::env::remove_var(INSTALL_DIR_ENV) };
+    assert_eq!(dir.unwrap(), PathBuf::from("/env/bin"));
+}
+
+#[test]
+#[serial]
+fn test_resolve_explicit_install_dir_overrides_env() {
+    unsafe { std::env::set_var(INSTALL_DIR_ENV, "/env/bin") };
+    let dir = resolve_install_dir(Some(Path::new("/custom/bin")), None);
+    unsafe { std::env::remove_var(INSTALL_DIR_ENV) };
+    assert_eq!(dir.unwrap(), PathBuf::from("/custom/bin"));
+}
+
+#[test]
+#[serial]
+fn test_resolve_test_dir_takes_precedence() {
+    let dir = resolve_install_dir(Some(Path::new("/custom/bin")), Some(Path::new("/test/bin")));
+    assert_eq!(dir.unwrap(), PathBuf::from("/test/bin"));
+}
+
+#[test]
+#[serial]
+fn test_expand_tilde_alone_and_with_subpath() {
+    let original = std::env::var("HOME").unwrap();
+    unsafe { std::env::set_var("HOME", "/home/tester") };
+    let bare = expand_tilde(Path::new("~"));
+    let sub = expand_tilde(Path::new("~/projects/ask"));
+    let other_user = expand_tilde(Path::new("~someone/bin"));
+    let absolute = expand_tilde(Path::new("/opt/bin"));
+    unsafe { std::env::set_var("HOME", original) };
+
+    assert_eq!(bare, PathBuf::from("/home/tester"));
+    assert_eq!(sub, PathBuf::from("/home/tester/projects/ask"));
+    assert_eq!(other_user, PathBuf::from("~someone/bin"));
+    assert_eq!(absolute, PathBuf::from("/opt/bin"));
+}
+
+#[test]
+#[serial]
+fn test_resolve_install_dir_expands_tilde() {
+    let original = std::env::var("HOME").unwrap();
+    unsafe { std::env::set_var("HOME", "/home/tester") };
+    let dir = resolve_install_dir(Some(Path::new("~/bin")), None);
+    unsafe { std::env::set_var("HOME", original) };
+    assert_eq!(dir.unwrap(), PathBuf::from("/home/tester/bin"));
+}
+
+#[test]
+#[serial]
+fn test_resolve_install_dir_expands_env_vars() {
+    unsafe { std::env::set_var("SW_INSTALL_TEST_ROOT", "/custom/data") };
+    let plain = resolve_install_dir(Some(Path::new("$SW_INSTALL_TEST_ROOT/bin")), None);
+    let braced = resolve_install_dir(Some(Path::new("${SW_INSTALL_TEST_ROOT}-x/bin")), None);
+    unsafe { std::env::remove_var("SW_INSTALL_TEST_ROOT") };
+    assert_eq!(plain.unwrap(), PathBuf::from("/custom/data/bin"));
+    assert_eq!(braced.unwrap(), PathBuf::from("/custom/data-x/bin"));
+}
+
+#[test]
+#[serial]
+fn test_expand_env_vars_keeps_unset_and_malformed_references() {
+    unsafe { std::env::remove_var("SW_INSTALL_UNSET_VAR") };
+    assert_eq!(
+        expand_env_vars("$SW_INSTALL_UNSET_VAR/bin"),
+        "$SW_INSTALL_UNSET_VAR/bin"
+    );
+    assert_eq!(expand_env_vars("${unterminated/bin"), "${unterminated/bin");
+    assert_eq!(expand_env_vars("cost$"), "cost$");
+}
+
+#[test]
+fn test_print_install_dir_default() {
+    let home = TempDir::new().unwrap();
+    let output = sw_install()
+        .arg("--print-install-dir")
+        .env("HOME", home.path())
+        .env_remove("SW_INSTALL_DIR")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let expected = home.path().join(".local/softwarewrighter/bin");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        expected.display().to_string()
+    );
+}
+
+#[test]
+fn test_print_install_dir_with_override() {
+    let output = run(&["--print-install-dir", "--install-dir", "/opt/tools/bin"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        "/opt/tools/bin"
+    );
+}
+
+#[test]
+fn test_project_path_with_literal_tilde_is_expanded() {
+    let home = TempDir::new().unwrap();
+    let project = home.path().join("projects").join("tools");
+    fs::create_dir_all(&project).unwrap();
+    create_two_tool_workspace(&project);
+    let bin_dir = TempDir::new().unwrap();
+
+    let output = sw_install()
+        .args(["-p", "~/projects/tools", "--bin", "tool-a", "-t"])
+        .arg(bin_dir.path())
+        .env("HOME", home.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert!(bin_dir.path().join("tool-a").exists());
+}
+
+#[test]
+fn test_install_dir_pointing_at_file_is_rejected() {
+    let project = TempDir::new().unwrap();
+    let scratch = TempDir::new().unwrap();
+    create_two_tool_workspace(project.path());
+    let not_a_dir = scratch.path().join("bin");
+    fs::write(&not_a_dir, "just a file").unwrap();
+
+    let output = run(&[
+        "-p",
+        project.path().to_str().unwrap(),
+        "-t",
+        not_a_dir.to_str().unwrap(),
+    ]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(&format!(
+        "Installation directory path is not a directory: {}",
+        not_a_dir.display()
+    )));
+    assert_eq!(fs::read_to_string(&not_a_dir).unwrap(), "just a file");
+}
+
+#[test]
+fn test_install_dir_relative_to_project() {
+    let project = TempDir::new().unwrap();
+    let home = TempDir::new().unwrap();
+    create_two_tool_workspace(project.path());
+
+    let output = sw_install()
+        .args(["--bin", "tool-a", "--install-dir", "@project/.bin", "-p"])
+        .arg(project.path())
+        .env("HOME", home.path())
+        .env_remove("SW_INSTALL_DIR")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    let installed = project.path().join(".bin").join("tool-a");
+    assert_eq!(fs::read_to_string(installed).unwrap(), "binary tool-a");
+    assert!(!project.path().join("@project").exists());
+}
@@ -0,0 +1,70 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+//! Tests for the tool registry and installing by alias.
+
+mod common;
+
+use common::{create_two_tool_workspace, sw_install};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+use sw_install::ToolRegistry;
+use tempfile::TempDir;
+
+#[test]
+fn test_tool_registry_round_trips() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("sw-install").join("tools.toml");
+    let mut registry = ToolRegistry::load(&path).unwrap();
+    registry.register("ask", PathBuf::from("/src/ask"));
+    registry.register("tool", PathBuf::from("/src/tool"));
+    registry.save(&path).unwrap();
+
+    let mut loaded = ToolRegistry::load(&path).unwrap();
+    assert_eq!(loaded.get("ask"), Some(Path::new("/src/ask")));
+    assert_eq!(loaded.unregister("tool"), Some(PathBuf::from("/src/tool")));
+    assert_eq!(loaded.get("tool"), None);
+}
+
+fn run_with_config(config_home: &Path, args: &[&str]) -> Output {
+    sw_install()
+        .args(args)
+        .env("XDG_CONFIG_HOME", config_home)
+        .output()
+        .expect("failed to run sw-install")
+}
+
+#[test]
+fn test_register_install_by_alias_and_unregister() {
+    let project = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    let config_home = TempDir::new().unwrap();
+    create_two_tool_workspace(project.path());
+    let project_arg = project.path().to_str().unwrap();
+    let bin_arg = bin_dir.path().to_str().unwrap();
+
+    let output = run_with_config(config_home.path(), &["--register", "suite", project_arg]);
+    assert!(output.status.success(), "{output:?}");
+    let registry = config_home.path().join("sw-install").join("tools.toml");
+    assert!(fs::read_to_string(&registry).unwrap().contains("suite"));
+
+    let args = ["--tool", "suite", "--bin", "tool-a", "--test-dir", bin_arg];
+    let output = run_with_config(config_home.path(), &args);
+    assert!(output.status.success(), "{output:?}");
+    assert!(bin_dir.path().join("tool-a").exists());
+
+    let output = run_with_config(config_home.path(), &["--unregister", "suite"]);
+    assert!(output.status.success(), "{output:?}");
+    let output = run_with_config(config_home.path(), &args);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No tool registered as 'suite'"));
+}
+
+#[test]
+fn test_unregister_unknown_tool_fails() {
+    let config_home = TempDir::new().unwrap();
+    let output = run_with_config(config_home.path(), &["--unregister", "missing"]);
+    assert!(!output.status.success());
+    assert!(!config_home.path().join("sw-install").exists());
+}
@@ -80,3 +80,69 @@ fn test_uninstall_dry_run_doesnt_remove() {
     assert!(result.is_ok());
     assert!(binary_path.exists()); // Binary should still exist
 }
+
+fn uninstall_with_sidecars(purge: bool) -> Vec<String> {
+    let bin_dir = TempDir::new().unwrap();
+    for file in ["app", "app.pdb", "app.bak-1700000000000", "app-other.pdb"] {
+        fs::write(bin_dir.path().join(file), "fake").unwrap();
+    }
+    fs::create_dir_all(bin_dir.path().join("app.dSYM").join("Contents")).unwrap();
+
+    let output = NormalOutput::default();
+    Uninstaller::new(
+        "app".to_string(),
+        false,
+        Some(bin_dir.path().to_path_buf()),
+        &output,
+    )
+    .with_purge(purge)
+    .uninstall()
+    .unwrap();
+    let mut left: Vec<String> = fs::read_dir(bin_dir.path())
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+        .filter(|name| !name.starts_with('.'))
+        .collect();
+    left.sort();
+    left
+}
+
+#[test]
+fn test_purge_removes_sidecars_and_backups() {
+    assert_eq!(uninstall_with_sidecars(true), vec!["app-other.pdb"]);
+}
+
+#[test]
+fn test_uninstall_without_purge_keeps_sidecars() {
+    assert_eq!(
+        uninstall_with_sidecars(false),
+        vec![
+            "app-other.pdb",
+            "app.bak-1700000000000",
+            "app.dSYM",
+            "app.pdb"
+        ]
+    );
+}
+
+#[test]
+fn test_uninstall_refuses_directory_at_binary_path() {
+    let temp_home = TempDir::new().unwrap();
+    let test_bin_dir = temp_home.path().join("bin");
+    let leftover = test_bin_dir.join("testapp");
+    fs::create_dir_all(leftover.join("stray")).unwrap();
+
+    let output = NormalOutput::default();
+    let error = Uninstaller::new(
+        "testapp".to_string(),
+        false,
+        Some(test_bin_dir.clone()),
+        &output,
+    )
+    .uninstall()
+    .unwrap_err();
+
+    assert!(matches!(error, InstallError::DestinationIsDirectory(ref p) if *p == leftover));
+    assert!(error.to_string().contains("is a directory; remove it"));
+    assert!(leftover.join("stray").exists());
+}
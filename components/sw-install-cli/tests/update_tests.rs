@@ -0,0 +1,193 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+//! Tests for resolving and checking updates of tracked binaries.
+
+mod common;
+
+use common::{install_fake, sw_install};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use sw_install::{NormalOutput, UpdateStatus, Updater};
+use tempfile::TempDir;
+
+/// Creates project `name` under `root` and installs a fake build of it
+/// into `bin_dir`. Returns the build.
+fn install_project(root: &Path, name: &str, bin_dir: &Path) -> PathBuf {
+    let project = root.join(name);
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("Cargo.toml"),
+        format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n"),
+    )
+    .unwrap();
+    fs::write(project.join("src").join("main.rs"), "fn main() {}").unwrap();
+    install_fake(&project, bin_dir, name)
+}
+
+#[test]
+fn test_update_check_reports_stale_binary() {
+    let root = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    let fresh = install_project(root.path(), "fresh", bin_dir.path());
+    let stale = install_project(root.path(), "stale", bin_dir.path());
+    fs::write(&stale, "stale v2").unwrap();
+
+    let output = NormalOutput::default();
+    let statuses = Updater::new(vec![], true, Some(bin_dir.path().to_path_buf()), &output)
+        .check()
+        .unwrap();
+    assert_eq!(
+        statuses,
+        vec![
+            ("fresh".to_string(), UpdateStatus::Current),
+            ("stale".to_string(), UpdateStatus::Stale),
+        ]
+    );
+    assert_eq!(
+        fs::read_to_string(bin_dir.path().join("stale")).unwrap(),
+        "binary stale"
+    );
+    assert!(fresh.exists());
+}
+
+#[test]
+fn test_update_check_reports_unknown_source() {
+    let bin_dir = TempDir::new().unwrap();
+    fs::write(bin_dir.path().join("manual"), "copied by hand").unwrap();
+    let output = NormalOutput::default();
+    sw_install::ManifestRepair::new(false, Some(bin_dir.path().to_path_buf()), &output)
+        .repair()
+        .unwrap();
+
+    let statuses = Updater::new(vec![], true, Some(bin_dir.path().to_path_buf()), &output)
+        .check()
+        .unwrap();
+    assert!(matches!(statuses[0].1, UpdateStatus::Unavailable(_)));
+}
+
+#[test]
+fn test_update_check_exit_code_and_update_reinstalls() {
+    let root = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    let source = install_project(root.path(), "tool", bin_dir.path());
+    fs::write(&source, "tool v2").unwrap();
+    let run = |args: &[&str]| {
+        sw_install()
+            .args(args)
+            .arg("--test-dir")
+            .arg(bin_dir.path())
+            .output()
+            .unwrap()
+    };
+
+    let check = run(&["--update", "--check"]);
+    assert!(!check.status.success());
+    assert!(String::from_utf8_lossy(&check.stdout).contains("tool: out of date"));
+
+    assert!(run(&["--update", "tool"]).status.success());
+    assert_eq!(
+        fs::read_to_string(bin_dir.path().join("tool")).unwrap(),
+        "tool v2"
+    );
+    assert!(run(&["--update", "--check"]).status.success());
+}
+
+fn touch_future(path: &Path) {
+    let later = std::time::SystemTime::now() + std::time::Duration::from_secs(60);
+    fs::File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_modified(later)
+        .unwrap();
+}
+
+fn install_only_if_newer(project: &Path, bin_dir: &Path) -> String {
+    let output = sw_install()
+        .args([
+            "-p",
+            project.to_str().unwrap(),
+            "-t",
+            bin_dir.to_str().unwrap(),
+        ])
+        .arg("--only-if-newer")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_only_if_newer_skips_identical_build() {
+    let root = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    let source = install_project(root.path(), "tool", bin_dir.path());
+    touch_future(&source);
+
+    let stdout = install_only_if_newer(&root.path().join("tool"), bin_dir.path());
+    assert!(stdout.contains("tool already current"), "{stdout}");
+}
+
+#[test]
+fn test_only_if_newer_installs_newer_build() {
+    let root = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    let source = install_project(root.path(), "tool", bin_dir.path());
+    fs::write(&source, "tool v2").unwrap();
+    touch_future(&source);
+
+    let stdout = install_only_if_newer(&root.path().join("tool"), bin_dir.path());
+    assert!(!stdout.contains("already current"), "{stdout}");
+    let installed = fs::read_to_string(bin_dir.path().join("tool")).unwrap();
+    assert_eq!(installed, "tool v2");
+}
+
+#[test]
+fn test_update_rebuild_installs_fresh_build_and_keeps_old_on_failure() {
+    if sw_install::require_tool("cargo").is_err() {
+        return;
+    }
+    let root = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    let project = root.path().join("greet");
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("Cargo.toml"),
+        "[package]\nname = \"greet\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace]\n",
+    )
+    .unwrap();
+    let main_rs = project.join("src").join("main.rs");
+    let set_message = |msg: &str| {
+        fs::write(&main_rs, format!("fn main() {{ println!(\"{msg}\"); }}")).unwrap();
+    };
+    let run = |args: &[&str]| {
+        sw_install()
+            .args(args)
+            .arg("--test-dir")
+            .arg(bin_dir.path())
+            .output()
+            .unwrap()
+    };
+    let installed_says = || {
+        let out = Command::new(bin_dir.path().join("greet")).output().unwrap();
+        String::from_utf8_lossy(&out.stdout).trim().to_string()
+    };
+
+    set_message("v1");
+    let output = run(&["-p", project.to_str().unwrap(), "--build"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(installed_says(), "v1");
+
+    set_message("v2");
+    let output = run(&["--update", "greet", "--rebuild"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(installed_says(), "v2");
+
+    fs::write(&main_rs, "fn main() { this does not compile }").unwrap();
+    let output = run(&["--update", "greet", "--rebuild"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Build failed"));
+    assert_eq!(installed_says(), "v2");
+}
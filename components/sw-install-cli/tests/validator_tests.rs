@@ -5,7 +5,7 @@
 
 use std::fs;
 use std::path::{Path, PathBuf};
-use sw_install::{InstallConfig, InstallError, NormalOutput, Validator};
+use sw_install::{CapturedOutput, InstallConfig, InstallError, NormalOutput, Validator};
 use tempfile::TempDir;
 
 fn create_test_project(dir: &Path, include_binary: bool) -> std::io::Result<()> {
@@ -116,6 +116,7 @@ fn test_validate_fails_when_cargo_toml_missing() {
 fn test_validate_fails_when_binary_missing() {
     let temp_dir = TempDir::new().unwrap();
     create_test_project(temp_dir.path(), false).unwrap();
+    fs::create_dir_all(temp_dir.path().join("target").join("release")).unwrap();
 
     let config = new_config(temp_dir.path().to_path_buf());
     let output = NormalOutput::default();
@@ -129,6 +130,43 @@ fn test_validate_fails_when_binary_missing() {
     ));
 }
 
+#[test]
+fn test_dry_run_miss_reports_checked_path() {
+    let temp_dir = TempDir::new().unwrap();
+    create_test_project(temp_dir.path(), false).unwrap();
+    fs::create_dir_all(temp_dir.path().join("target").join("release")).unwrap();
+
+    let mut config = new_config(temp_dir.path().to_path_buf());
+    config.dry_run = true;
+    let captured = CapturedOutput::default();
+    let output = NormalOutput::new(false, true).with_handler(captured.clone());
+    let error = Validator::new(&config, &output).validate().unwrap_err();
+
+    let InstallError::BinaryNotFound(path) = error else {
+        panic!("expected BinaryNotFound, got {error:?}");
+    };
+    let hint = format!(
+        "Warning: Looked for test-app (release build) at {}",
+        path.display()
+    );
+    assert_eq!(captured.lines(), vec![hint]);
+}
+
+#[test]
+fn test_validate_fails_when_binary_empty() {
+    let temp_dir = TempDir::new().unwrap();
+    create_test_project(temp_dir.path(), false).unwrap();
+    let target_dir = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&target_dir).unwrap();
+    fs::write(target_dir.join("test-app"), "").unwrap();
+
+    let config = new_config(temp_dir.path().to_path_buf());
+    let output = NormalOutput::default();
+    let error = Validator::new(&config, &output).validate().unwrap_err();
+    assert!(matches!(error, InstallError::EmptyBinary(_)));
+    assert!(error.to_string().contains("cargo build --release"));
+}
+
 #[test]
 fn test_validate_succeeds_with_valid_project() {
     let temp_dir = TempDir::new().unwrap();
@@ -291,3 +329,101 @@ fn test_multi_component_project_detection() {
     assert_eq!(binaries.len(), 1);
     assert_eq!(binaries[0].0, "my-app");
 }
+
+fn set_mtime(path: &Path, time: std::time::SystemTime) {
+    fs::File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_modified(time)
+        .unwrap();
+}
+
+fn validate_with_source_newer_by(
+    secs: u64,
+    allow_outdated: bool,
+) -> (Result<(), InstallError>, Vec<String>) {
+    let temp_dir = TempDir::new().unwrap();
+    create_test_project(temp_dir.path(), true).unwrap();
+    let main_rs = temp_dir.path().join("src").join("main.rs");
+    fs::create_dir_all(main_rs.parent().unwrap()).unwrap();
+    fs::write(&main_rs, "fn main() {}").unwrap();
+    let built = std::time::SystemTime::now() - std::time::Duration::from_secs(60);
+    let binary = temp_dir.path().join("target/release/test-app");
+    set_mtime(&binary, built);
+    set_mtime(&main_rs, built + std::time::Duration::from_secs(secs));
+
+    let mut config = new_config(temp_dir.path().to_path_buf());
+    config.allow_outdated = allow_outdated;
+    let captured = CapturedOutput::default();
+    let output = NormalOutput::default().with_handler(captured.clone());
+    let result = Validator::new(&config, &output).validate().map(|_| ());
+    (result, captured.lines())
+}
+
+#[test]
+fn test_source_within_freshness_tolerance_is_accepted() {
+    assert!(validate_with_source_newer_by(1, false).0.is_ok());
+}
+
+#[test]
+fn test_source_beyond_freshness_tolerance_is_outdated() {
+    assert!(matches!(
+        validate_with_source_newer_by(10, false).0,
+        Err(InstallError::BinaryOutdated(_))
+    ));
+}
+
+#[test]
+fn test_autobins_false_installs_only_declared_bin() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"pkg\"\nversion = \"0.1.0\"\nautobins = false\n\n\
+         [[bin]]\nname = \"declared\"\npath = \"src/main.rs\"\n",
+    )
+    .unwrap();
+    fs::create_dir_all(dir.join("src").join("bin")).unwrap();
+    fs::write(dir.join("src").join("bin").join("extra.rs"), "fn main() {}").unwrap();
+    let target_dir = dir.join("target").join("release");
+    fs::create_dir_all(&target_dir).unwrap();
+    fs::write(target_dir.join("declared"), "fake binary").unwrap();
+
+    let config = new_config(dir.to_path_buf());
+    let output = NormalOutput::default();
+    let binaries = Validator::new(&config, &output)
+        .validate()
+        .unwrap()
+        .binaries;
+    let names: Vec<&str> = binaries.iter().map(|(n, _)| n.as_str()).collect();
+    assert_eq!(names, vec!["declared"]);
+}
+
+#[test]
+fn test_allow_outdated_warns_and_proceeds() {
+    let (result, lines) = validate_with_source_newer_by(10, true);
+    assert!(result.is_ok());
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.starts_with("Warning: Binary is older than source files"))
+    );
+}
+
+#[test]
+fn test_validate_reports_missing_profile_build_early() {
+    let temp_dir = TempDir::new().unwrap();
+    create_test_project(temp_dir.path(), true).unwrap();
+
+    let mut config = new_config(temp_dir.path().to_path_buf());
+    config.use_debug = true;
+    let captured = CapturedOutput::default();
+    let output = NormalOutput::new(true, false).with_handler(captured.clone());
+    let error = Validator::new(&config, &output).validate().unwrap_err();
+
+    assert!(matches!(error, InstallError::ProfileNotBuilt(ref p, _) if p == "debug"));
+    assert!(error.to_string().starts_with("No debug build found: "));
+    assert!(error.to_string().contains("Did you run 'cargo build'?"));
+    assert!(!captured.lines().iter().any(|l| l.contains("Verifying")));
+}
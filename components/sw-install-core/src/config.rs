@@ -1,19 +1,80 @@
 // Copyright (c) 2025 Michael A Wright
 // Licensed under the MIT License
 
-use crate::{InstallError, Result};
-use std::path::PathBuf;
+use crate::{InstallLayout, Result, check_binary_name, resolve_install_dir};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Bound on each external command (build, hook, clone); generous so slow
+/// builds are never cut short by default.
+pub const DEFAULT_COMMAND_TIMEOUT: Duration = Duration::from_secs(3600);
+
+/// Absorbs coarse or skewed timestamps on filesystems such as FAT and NFS.
+pub const DEFAULT_FRESHNESS_TOLERANCE: Duration = Duration::from_secs(2);
+
+/// What the installer does when the installation directory is missing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DirPolicy {
+    /// Create an explicitly chosen dir, but refuse to create the default
+    /// one whose parent is missing, pointing at `--setup-install-dir`.
+    #[default]
+    RequireSetup,
+    /// Create whichever directory is missing, the default one included.
+    Create,
+}
 
 #[derive(Debug, Clone)]
 pub struct InstallConfig {
     pub project_path: PathBuf,
     pub rename: Option<String>,
+    /// Per-binary rename such as `sw-{name}`; `{name}` is the cargo name.
+    pub rename_template: Option<String>,
+    /// Per-binary installed names keyed by cargo name; unmapped binaries
+    /// fall back to `rename_template` or keep their own name.
+    pub rename_map: BTreeMap<String, String>,
     pub bin_filter: Vec<String>,
     pub use_debug: bool,
+    /// Switch to whichever of the release or debug build holds the
+    /// binaries (the newer if both do) instead of failing on `use_debug`.
+    pub auto_profile: bool,
     pub verbose: bool,
     pub dry_run: bool,
     pub build: bool,
     pub test_dir: Option<PathBuf>,
+    pub install_dir: Option<PathBuf>,
+    pub components_dir: Option<String>,
+    pub component: Option<String>,
+    pub example: Option<String>,
+    pub post_install: Option<String>,
+    /// Copy an existing destination binary aside before overwriting it.
+    pub backup: bool,
+    /// How many backups per binary to retain.
+    pub keep_backups: usize,
+    pub layout: InstallLayout,
+    /// How much newer a source file may be before the binary counts as outdated.
+    pub freshness_tolerance: Duration,
+    /// Warn instead of failing when the binary is older than its sources.
+    pub allow_outdated: bool,
+    /// Allow installing into a system bin directory such as /usr/bin, and
+    /// replacing a binary the manifest records from another project.
+    pub force: bool,
+    /// Emit NDJSON step events instead of human-readable progress.
+    pub progress_json: bool,
+    /// How long `--build` and post-install hooks may run.
+    pub command_timeout: Duration,
+    /// Skip binaries whose installed copy is already current.
+    pub only_if_newer: bool,
+    /// Mark installed binaries executable; off for mounts that reject
+    /// mode changes or installs run through a wrapper.
+    pub set_executable: bool,
+    pub dir_policy: DirPolicy,
+    /// Fail instead of choosing when detection finds several components or
+    /// binaries and none was selected explicitly.
+    pub strict_detection: bool,
+    /// Explicitly selects every detected binary, satisfying
+    /// `strict_detection` without naming each one.
+    pub install_all: bool,
 }
 
 impl InstallConfig {
@@ -31,31 +92,240 @@ impl InstallConfig {
         Self {
             project_path,
             rename,
+            rename_template: None,
+            rename_map: BTreeMap::new(),
             bin_filter,
             use_debug,
+            auto_profile: false,
             verbose,
             dry_run,
             build,
             test_dir,
+            install_dir: None,
+            components_dir: None,
+            component: None,
+            example: None,
+            post_install: None,
+            backup: false,
+            keep_backups: 1,
+            layout: InstallLayout::Flat,
+            freshness_tolerance: DEFAULT_FRESHNESS_TOLERANCE,
+            allow_outdated: false,
+            force: false,
+            progress_json: false,
+            command_timeout: DEFAULT_COMMAND_TIMEOUT,
+            only_if_newer: false,
+            set_executable: true,
+            dir_policy: DirPolicy::RequireSetup,
+            strict_detection: false,
+            install_all: false,
         }
     }
 
-    pub fn destination_dir(&self) -> Result<PathBuf> {
-        if let Some(ref test_dir) = self.test_dir {
-            return Ok(test_dir.clone());
+    /// Starts a builder for installing from `project_path`, with every
+    /// other setting at its default.
+    pub fn builder(project_path: impl Into<PathBuf>) -> InstallConfigBuilder {
+        InstallConfigBuilder {
+            config: Self::new(
+                project_path.into(),
+                None,
+                Vec::new(),
+                false,
+                false,
+                false,
+                false,
+                None,
+            ),
         }
-        let home = std::env::var("HOME").map_err(|_| InstallError::HomeNotFound)?;
-        Ok(PathBuf::from(home)
-            .join(".local")
-            .join("softwarewrighter")
-            .join("bin"))
+    }
+
+    pub fn destination_dir(&self) -> Result<PathBuf> {
+        resolve_install_dir(self.install_dir.as_deref(), self.test_dir.as_deref())
+    }
+
+    /// Name cargo binary `binary` is installed under, after any rename.
+    pub fn installed_name(&self, binary: &str) -> Result<String> {
+        let name = match (&self.rename, &self.rename_template) {
+            (Some(rename), _) => rename.clone(),
+            _ if self.rename_map.contains_key(binary) => self.rename_map[binary].clone(),
+            (None, Some(template)) => template.replace("{name}", binary),
+            (None, None) => binary.to_string(),
+        };
+        check_binary_name(&name)?;
+        Ok(name)
     }
 
     pub fn source_binary_path(&self, actual_name: &str) -> PathBuf {
-        let subdir = if self.use_debug { "debug" } else { "release" };
-        self.project_path
-            .join("target")
-            .join(subdir)
-            .join(actual_name)
+        self.output_dir(&self.project_path).join(actual_name)
+    }
+
+    /// Cargo profile directory name for this build: `debug` or `release`.
+    pub fn profile(&self) -> &'static str {
+        if self.use_debug { "debug" } else { "release" }
+    }
+
+    /// Directory cargo writes this build's binaries to under `build_root`.
+    pub fn output_dir(&self, build_root: &Path) -> PathBuf {
+        let dir = build_root.join("target").join(self.profile());
+        if self.example.is_some() {
+            dir.join("examples")
+        } else {
+            dir
+        }
+    }
+}
+
+/// Named-setter alternative to the positional [`InstallConfig::new`];
+/// each setter overrides one field's default.
+#[derive(Debug, Clone)]
+pub struct InstallConfigBuilder {
+    config: InstallConfig,
+}
+
+impl InstallConfigBuilder {
+    pub fn rename(mut self, rename: Option<String>) -> Self {
+        self.config.rename = rename;
+        self
+    }
+
+    pub fn rename_template(mut self, rename_template: Option<String>) -> Self {
+        self.config.rename_template = rename_template;
+        self
+    }
+
+    pub fn rename_map(mut self, rename_map: BTreeMap<String, String>) -> Self {
+        self.config.rename_map = rename_map;
+        self
+    }
+
+    pub fn bin_filter(mut self, bin_filter: Vec<String>) -> Self {
+        self.config.bin_filter = bin_filter;
+        self
+    }
+
+    pub fn use_debug(mut self, use_debug: bool) -> Self {
+        self.config.use_debug = use_debug;
+        self
+    }
+
+    pub fn auto_profile(mut self, auto_profile: bool) -> Self {
+        self.config.auto_profile = auto_profile;
+        self
+    }
+
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.config.verbose = verbose;
+        self
+    }
+
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.config.dry_run = dry_run;
+        self
+    }
+
+    /// Sets `build`, running cargo before installing; named apart from
+    /// [`Self::build`].
+    pub fn run_build(mut self, build: bool) -> Self {
+        self.config.build = build;
+        self
+    }
+
+    pub fn test_dir(mut self, test_dir: Option<PathBuf>) -> Self {
+        self.config.test_dir = test_dir;
+        self
+    }
+
+    pub fn install_dir(mut self, install_dir: Option<PathBuf>) -> Self {
+        self.config.install_dir = install_dir;
+        self
+    }
+
+    pub fn components_dir(mut self, components_dir: Option<String>) -> Self {
+        self.config.components_dir = components_dir;
+        self
+    }
+
+    pub fn component(mut self, component: Option<String>) -> Self {
+        self.config.component = component;
+        self
+    }
+
+    pub fn example(mut self, example: Option<String>) -> Self {
+        self.config.example = example;
+        self
+    }
+
+    pub fn post_install(mut self, post_install: Option<String>) -> Self {
+        self.config.post_install = post_install;
+        self
+    }
+
+    pub fn backup(mut self, backup: bool) -> Self {
+        self.config.backup = backup;
+        self
+    }
+
+    pub fn keep_backups(mut self, keep_backups: usize) -> Self {
+        self.config.keep_backups = keep_backups;
+        self
+    }
+
+    pub fn layout(mut self, layout: InstallLayout) -> Self {
+        self.config.layout = layout;
+        self
+    }
+
+    pub fn freshness_tolerance(mut self, freshness_tolerance: Duration) -> Self {
+        self.config.freshness_tolerance = freshness_tolerance;
+        self
+    }
+
+    pub fn allow_outdated(mut self, allow_outdated: bool) -> Self {
+        self.config.allow_outdated = allow_outdated;
+        self
+    }
+
+    pub fn force(mut self, force: bool) -> Self {
+        self.config.force = force;
+        self
+    }
+
+    pub fn progress_json(mut self, progress_json: bool) -> Self {
+        self.config.progress_json = progress_json;
+        self
+    }
+
+    pub fn command_timeout(mut self, command_timeout: Duration) -> Self {
+        self.config.command_timeout = command_timeout;
+        self
+    }
+
+    pub fn only_if_newer(mut self, only_if_newer: bool) -> Self {
+        self.config.only_if_newer = only_if_newer;
+        self
+    }
+
+    pub fn set_executable(mut self, set_executable: bool) -> Self {
+        self.config.set_executable = set_executable;
+        self
+    }
+
+    pub fn dir_policy(mut self, dir_policy: DirPolicy) -> Self {
+        self.config.dir_policy = dir_policy;
+        self
+    }
+
+    pub fn strict_detection(mut self, strict_detection: bool) -> Self {
+        self.config.strict_detection = strict_detection;
+        self
+    }
+
+    pub fn install_all(mut self, install_all: bool) -> Self {
+        self.config.install_all = install_all;
+        self
+    }
+
+    pub fn build(self) -> InstallConfig {
+        self.config
     }
 }
@@ -0,0 +1,21 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use crate::{InstallError, Result};
+use std::path::Path;
+
+/// Attaches the failing step and path to an IO error.
+pub trait IoResultExt<T> {
+    /// `step` reads as "Failed while <step> <path>", e.g. "copying binary to".
+    fn io_context(self, step: &'static str, path: &Path) -> Result<T>;
+}
+
+impl<T> IoResultExt<T> for std::io::Result<T> {
+    fn io_context(self, step: &'static str, path: &Path) -> Result<T> {
+        self.map_err(|source| InstallError::IoContext {
+            step,
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+//! Destinations for `NormalOutput` messages.
+
+use crate::json_string;
+use std::sync::{Arc, Mutex};
+
+/// Phase of an operation step reported through `NormalOutput::step`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepStatus {
+    Start,
+    Done,
+}
+
+/// Where `NormalOutput` sends lines once its mode has decided to show them.
+pub trait OutputHandler: Send + Sync {
+    /// Regular progress and result messages.
+    fn out(&self, line: &str);
+    /// Warnings and other diagnostics.
+    fn err(&self, line: &str);
+    /// Structured step transitions; human-oriented handlers ignore them.
+    fn step(&self, _step: &str, _status: StepStatus) {}
+}
+
+/// Prints to stdout and stderr; the default handler.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ConsoleOutput;
+
+impl OutputHandler for ConsoleOutput {
+    fn out(&self, line: &str) {
+        println!("{}", line);
+    }
+
+    fn err(&self, line: &str) {
+        eprintln!("{}", line);
+    }
+}
+
+/// Records every line in memory; clones share the same buffer, so keep
+/// one clone to read what a component printed through the other.
+#[derive(Debug, Default, Clone)]
+pub struct CapturedOutput {
+    lines: Arc<Mutex<Vec<String>>>,
+}
+
+impl CapturedOutput {
+    pub fn lines(&self) -> Vec<String> {
+        self.lines.lock().map(|l| l.clone()).unwrap_or_default()
+    }
+}
+
+impl OutputHandler for CapturedOutput {
+    fn out(&self, line: &str) {
+        if let Ok(mut lines) = self.lines.lock() {
+            lines.push(line.to_string());
+        }
+    }
+
+    fn err(&self, line: &str) {
+        self.out(line);
+    }
+}
+
+/// Newline-delimited JSON step events on stdout for automation, in place
+/// of human text; warnings still reach stderr.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct JsonProgressOutput;
+
+impl OutputHandler for JsonProgressOutput {
+    fn out(&self, _line: &str) {}
+
+    fn err(&self, line: &str) {
+        eprintln!("{}", line);
+    }
+
+    fn step(&self, step: &str, status: StepStatus) {
+        let status = match status {
+            StepStatus::Start => "start",
+            StepStatus::Done => "done",
+        };
+        println!(
+            "{{\"step\":{},\"status\":{}}}",
+            json_string(step),
+            json_string(status)
+        );
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use crate::{Result, is_backup_name};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// How binaries are arranged inside the installation directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InstallLayout {
+    /// `<dest>/<name>`
+    #[default]
+    Flat,
+    /// `<dest>/<name>/<name>`
+    Nested,
+}
+
+impl InstallLayout {
+    pub fn binary_path(self, dest_dir: &Path, name: &str) -> PathBuf {
+        match self {
+            InstallLayout::Flat => dest_dir.join(name),
+            InstallLayout::Nested => dest_dir.join(name).join(name),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InvalidInstallLayout(pub String);
+
+impl std::fmt::Display for InvalidInstallLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid layout '{}'. Valid options: flat, nested",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for InvalidInstallLayout {}
+
+impl FromStr for InstallLayout {
+    type Err = InvalidInstallLayout;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "flat" => Ok(InstallLayout::Flat),
+            "nested" => Ok(InstallLayout::Nested),
+            _ => Err(InvalidInstallLayout(s.to_string())),
+        }
+    }
+}
+
+/// Locates an installed `name` under either layout, preferring flat.
+pub fn find_installed_binary(dest_dir: &Path, name: &str) -> Option<PathBuf> {
+    [InstallLayout::Flat, InstallLayout::Nested]
+        .into_iter()
+        .map(|layout| layout.binary_path(dest_dir, name))
+        .find(|path| path.is_file())
+}
+
+/// What one pass over an installation directory found.
+#[derive(Debug, Default)]
+pub struct InstalledEntries {
+    /// Every installed binary as `(name, path)`, in either layout.
+    pub binaries: Vec<(String, PathBuf)>,
+    /// Entries that look installed but cannot be stat'd, e.g. for lack of
+    /// permission or a dangling link, with the reason.
+    pub unreadable: Vec<(PathBuf, io::Error)>,
+}
+
+/// Every installed binary in `dest_dir` as `(name, path)`, in either layout.
+///
+/// Dotfiles (sw-install bookkeeping) and `--backup` copies are skipped, as
+/// are entries that cannot be stat'd.
+pub fn installed_binaries(dest_dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    Ok(scan_installed(dest_dir)?.binaries)
+}
+
+/// Reads `dest_dir` once, sorting its entries into installed binaries and
+/// unreadable ones. Skips the same entries as `installed_binaries`.
+pub fn scan_installed(dest_dir: &Path) -> Result<InstalledEntries> {
+    let mut found = InstalledEntries::default();
+    for entry in fs::read_dir(dest_dir)?.filter_map(|e| e.ok()) {
+        let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        if name.starts_with('.') || is_backup_name(&name) {
+            continue;
+        }
+        let path = entry.path();
+        let (binary, metadata) = match fs::metadata(&path) {
+            Ok(m) if m.is_dir() => {
+                let nested = InstallLayout::Nested.binary_path(dest_dir, &name);
+                match fs::metadata(&nested) {
+                    // A directory without a nested binary is not an install
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                    metadata => (nested, metadata),
+                }
+            }
+            metadata => (path, metadata),
+        };
+        match metadata {
+            Ok(m) if m.is_file() => found.binaries.push((name, binary)),
+            Ok(_) => {}
+            Err(e) => found.unreadable.push((binary, e)),
+        }
+    }
+    Ok(found)
+}
+
+/// `--backup` copies in `dest_dir` as `(name, path)`, including those kept
+/// beside nested binaries.
+pub fn installed_archives(dest_dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let mut dirs = vec![dest_dir.to_path_buf()];
+    dirs.extend(
+        fs::read_dir(dest_dir)?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_dir()),
+    );
+    Ok(dirs
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.filter_map(|e| e.ok()))
+        .filter_map(|e| {
+            let name = e.file_name().to_str()?.to_string();
+            (is_backup_name(&name) && e.path().is_file()).then(|| (name, e.path()))
+        })
+        .collect())
+}
@@ -4,8 +4,12 @@
 //! Core types for sw-install: configuration, output handling, and errors.
 
 mod config;
+mod context;
 mod format;
+mod handler;
+mod layout;
 mod output;
+mod paths;
 
 use std::path::PathBuf;
 use thiserror::Error;
@@ -21,6 +25,17 @@ pub enum InstallError {
     #[error("Cargo.toml not found in project: {0}")]
     CargoTomlNotFound(PathBuf),
 
+    #[error(
+        "Unsupported project layout: {0}\nHint: Expected a Cargo.toml with [package] or [workspace], or components/<name>/Cargo.toml with a binary"
+    )]
+    UnsupportedProjectLayout(PathBuf),
+
+    #[error("Component not found: {0}")]
+    ComponentNotFound(String),
+
+    #[error("Component has no binary: {0}")]
+    ComponentHasNoBinary(PathBuf),
+
     #[error("Could not parse Cargo.toml: {0}")]
     CargoTomlParse(String),
 
@@ -30,22 +45,85 @@ pub enum InstallError {
     #[error("Binary not found in workspace: {0}")]
     BinaryNotInWorkspace(String),
 
+    #[error(
+        "Binary '{0}' is declared by more than one workspace member: {1}\nHint: Install one member with --project <workspace>/<member>, or rename one of the [[bin]] targets"
+    )]
+    DuplicateWorkspaceBinary(String, String),
+
     #[error("--rename requires exactly one binary, but found {0}")]
     RenameMultipleBinaries(usize),
 
+    #[error(
+        "Could not read rename map {0}: {1}\nHint: The file should hold lines like `cargo-name = \"installed-name\"`"
+    )]
+    RenameMapParse(PathBuf, String),
+
+    #[error(
+        "{0} warning(s) emitted with --fail-on-warning\nHint: Fix the warnings above, or drop --fail-on-warning"
+    )]
+    WarningsAsErrors(usize),
+
     #[error("Build failed")]
     BuildFailed,
 
+    #[error("Required tool not found: {0}\nHint: Install {0} and make sure it is on PATH")]
+    ToolNotFound(String),
+
+    #[error("git clone failed: {0}")]
+    GitCloneFailed(String),
+
+    #[error("Could not parse .sw-install.toml: {0}")]
+    ProjectConfigParse(String),
+
+    #[error("Post-install hook failed: {0}")]
+    PostInstallFailed(String),
+
     #[error(
         "Source binary not found: {0}\nHint: Run 'cargo build --release' in the project directory"
     )]
     BinaryNotFound(PathBuf),
 
+    #[error(
+        "No {0} build found: {1}\nHint: Did you run 'cargo build{}'?",
+        if .0 == "release" { " --release" } else { "" }
+    )]
+    ProfileNotBuilt(String, PathBuf),
+
+    #[error(
+        "Example binary not found: {1}\nHint: Run 'cargo build --release --example {0}' in the project directory"
+    )]
+    ExampleNotFound(String, PathBuf),
+
+    #[error(
+        "Source binary is empty: {0}\nHint: The last build may have failed; run 'cargo build --release' again"
+    )]
+    EmptyBinary(PathBuf),
+
     #[error(
         "Binary is older than source files: {0}\nHint: Run 'cargo build --release' in the project directory"
     )]
     BinaryOutdated(PathBuf),
 
+    #[error(
+        "'{0}' is already installed from {1}\nHint: Pick another name with --rename, or pass --force to replace it"
+    )]
+    NameTaken(String, PathBuf),
+
+    #[error(
+        "Cannot find the sw-install source project for {0}\nHint: Run --self-install from a binary built in a sw-install checkout, or install the checkout with -p <checkout>/components/sw-install-cli"
+    )]
+    SelfSourceNotFound(PathBuf),
+
+    #[error(
+        "Several components contain binaries: {0}\nHint: --strict-detection needs one chosen with --component <NAME>"
+    )]
+    AmbiguousComponent(String),
+
+    #[error(
+        "Several binaries detected: {0}\nHint: --strict-detection needs --bin <NAME> for each one to install, or --all"
+    )]
+    AmbiguousBinaries(String),
+
     #[error("Binary not installed: {0}")]
     BinaryNotInstalled(String),
 
@@ -54,9 +132,55 @@ pub enum InstallError {
     )]
     InstallDirNotFound(PathBuf),
 
+    #[error(
+        "Installation directory path is not a directory: {0}\nHint: Remove the file or choose another --install-dir"
+    )]
+    InstallDirNotADirectory(PathBuf),
+
+    #[error("Directory to list does not exist: {0}")]
+    ListDirNotFound(PathBuf),
+
+    #[error(
+        "Refusing to install into system directory: {0}\nHint: Binaries there can shadow system tools; pass --force if this is intended"
+    )]
+    SystemInstallDir(PathBuf),
+
+    #[error(
+        "Cannot write shell config: {0}\nHint: Add this line to it manually, or re-run with --no-path-setup:\n  {1}"
+    )]
+    ShellConfigNotWritable(PathBuf, String),
+
+    #[error("Could not parse install manifest: {0}")]
+    ManifestParse(String),
+
+    #[error("Could not parse tool registry: {0}")]
+    RegistryParse(String),
+
+    #[error(
+        "No tool registered as '{0}'\nHint: Register it with 'sw-install --register {0} <PATH>'"
+    )]
+    UnknownTool(String),
+
+    #[error("Updates available: {0} installed binary(s) out of date")]
+    UpdatesAvailable(usize),
+
+    #[error("Failed while {step} {path}: {source}")]
+    IoContext {
+        step: &'static str,
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Command timed out after {1}s: {0}\nHint: Raise the limit with --timeout <SECS>")]
+    CommandTimedOut(String, u64),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
+    #[error("Destination {0} is a directory; remove it or choose a different name")]
+    DestinationIsDirectory(PathBuf),
+
     #[error("Invalid binary name: {0}")]
     InvalidBinaryName(String),
 
@@ -69,6 +193,20 @@ pub enum InstallError {
 
 pub type Result<T> = std::result::Result<T, InstallError>;
 
-pub use config::InstallConfig;
-pub use format::format_time_ago;
+pub use config::{
+    DEFAULT_COMMAND_TIMEOUT, DEFAULT_FRESHNESS_TOLERANCE, DirPolicy, InstallConfig,
+    InstallConfigBuilder,
+};
+pub use context::IoResultExt;
+pub use format::{JSON_SCHEMA_VERSION, format_size, format_time_ago, json_string, pretty_json};
+pub use handler::{CapturedOutput, ConsoleOutput, JsonProgressOutput, OutputHandler, StepStatus};
+pub use layout::{
+    InstallLayout, InstalledEntries, InvalidInstallLayout, find_installed_binary,
+    installed_archives, installed_binaries, scan_installed,
+};
 pub use output::NormalOutput;
+pub use paths::{
+    BACKUP_MARKER, INSTALL_DIR_ENV, backup_source, check_binary_name, default_install_dir,
+    expand_env_vars, expand_path, expand_tilde, is_backup_name, is_dir_on_path, path_dirs,
+    resolve_install_dir,
+};
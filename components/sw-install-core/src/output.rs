@@ -1,15 +1,28 @@
 // Copyright (c) 2025 Michael A Wright
 // Licensed under the MIT License
 
+use crate::handler::{ConsoleOutput, OutputHandler, StepStatus};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// Warnings emitted by any output in this process, for `--fail-on-warning`.
+static WARNINGS_EMITTED: AtomicUsize = AtomicUsize::new(0);
+
 #[derive(Debug, Clone, Copy)]
 enum OutputMode {
     Normal,
     Verbose,
+    Quiet,
     DryRun { verbose: bool },
 }
 
 pub struct NormalOutput {
     mode: OutputMode,
+    handler: Box<dyn OutputHandler>,
+    /// When each in-progress step started, for verbose phase timings.
+    step_started: Mutex<HashMap<String, Instant>>,
 }
 
 impl NormalOutput {
@@ -19,22 +32,92 @@ impl NormalOutput {
             (false, true) => OutputMode::Verbose,
             (false, false) => OutputMode::Normal,
         };
-        Self { mode }
+        Self {
+            mode,
+            handler: Box::new(ConsoleOutput),
+            step_started: Mutex::default(),
+        }
+    }
+
+    /// Sends messages to `handler` instead of the console.
+    pub fn with_handler(mut self, handler: impl OutputHandler + 'static) -> Self {
+        self.handler = Box::new(handler);
+        self
+    }
+
+    /// Suppresses info and success messages; warnings still print.
+    pub fn quiet() -> Self {
+        Self {
+            mode: OutputMode::Quiet,
+            handler: Box::new(ConsoleOutput),
+            step_started: Mutex::default(),
+        }
     }
 
     pub fn info(&self, message: &str) {
         match self.mode {
-            OutputMode::Normal => {}
-            OutputMode::Verbose => println!("{}", message),
-            OutputMode::DryRun { verbose: true } => println!("Would: {}", message),
+            OutputMode::Normal | OutputMode::Quiet => {}
+            OutputMode::Verbose => self.handler.out(message),
+            OutputMode::DryRun { verbose: true } => {
+                self.handler.out(&format!("Would: {}", message))
+            }
             OutputMode::DryRun { verbose: false } => {}
         }
     }
 
+    /// Advisory problems go to stderr in every mode, including dry runs.
+    pub fn warn(&self, message: &str) {
+        WARNINGS_EMITTED.fetch_add(1, Ordering::Relaxed);
+        self.handler.err(&format!("Warning: {}", message));
+    }
+
+    /// How many warnings every output in this process has emitted so far;
+    /// operations build their own outputs, so the count is process-wide.
+    pub fn warnings_emitted() -> usize {
+        WARNINGS_EMITTED.load(Ordering::Relaxed)
+    }
+
+    /// Reports a step transition to handlers that track progress; in
+    /// verbose mode a finished step also prints how long it took.
+    pub fn step(&self, step: &str, status: StepStatus) {
+        self.handler.step(step, status);
+        let Ok(mut started) = self.step_started.lock() else {
+            return;
+        };
+        match status {
+            StepStatus::Start => {
+                started.insert(step.to_string(), Instant::now());
+            }
+            StepStatus::Done => {
+                if let Some(start) = started.remove(step) {
+                    self.timing(step, start);
+                }
+            }
+        }
+    }
+
+    /// Runs `f`, printing its duration in verbose mode, for phases too
+    /// fine-grained to report as progress steps.
+    pub fn timed<T>(&self, phase: &str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.timing(phase, start);
+        result
+    }
+
+    fn timing(&self, phase: &str, start: Instant) {
+        if matches!(self.mode, OutputMode::Verbose) {
+            let elapsed = start.elapsed();
+            self.handler
+                .out(&format!("Timing: {phase} took {elapsed:.2?}"));
+        }
+    }
+
     pub fn success(&self, message: &str) {
         match self.mode {
-            OutputMode::Normal | OutputMode::Verbose => println!("{}", message),
-            OutputMode::DryRun { .. } => println!("Would: {}", message),
+            OutputMode::Normal | OutputMode::Verbose => self.handler.out(message),
+            OutputMode::DryRun { .. } => self.handler.out(&format!("Would: {}", message)),
+            OutputMode::Quiet => {}
         }
     }
 }
@@ -0,0 +1,132 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use crate::{InstallError, Result};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Marker separating a binary name from its backup timestamp.
+pub const BACKUP_MARKER: &str = ".bak-";
+
+/// Whether `name` is a `--backup` copy rather than an installed binary.
+pub fn is_backup_name(name: &str) -> bool {
+    backup_source(name).is_some()
+}
+
+/// The binary that `name` backs up, when `name` has the form
+/// `<binary>.bak-<digits>`. Other names merely containing the marker, such
+/// as `db.bak-restore`, are ordinary binaries.
+pub fn backup_source(name: &str) -> Option<&str> {
+    let (binary, stamp) = name.rsplit_once(BACKUP_MARKER)?;
+    let timestamped = !stamp.is_empty() && stamp.bytes().all(|b| b.is_ascii_digit());
+    (timestamped && !binary.is_empty()).then_some(binary)
+}
+
+/// Rejects names that would make an awkward or unsafe destination filename.
+/// Allows ASCII letters, digits, `-`, `_`, `.` and `+`, without a leading dot.
+pub fn check_binary_name(name: &str) -> Result<()> {
+    let allowed = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '+');
+    if name.is_empty() || name.starts_with('.') || !name.chars().all(allowed) {
+        return Err(InstallError::InvalidBinaryName(name.to_string()));
+    }
+    Ok(())
+}
+
+/// Environment variable that overrides the default installation directory.
+pub const INSTALL_DIR_ENV: &str = "SW_INSTALL_DIR";
+
+pub fn default_install_dir() -> Result<PathBuf> {
+    let home = std::env::var("HOME").map_err(|_| InstallError::HomeNotFound)?;
+    Ok(PathBuf::from(home)
+        .join(".local")
+        .join("softwarewrighter")
+        .join("bin"))
+}
+
+/// Resolves the effective installation directory.
+///
+/// Precedence: test dir, then `--install-dir`, then `$SW_INSTALL_DIR`, then
+/// `~/.local/softwarewrighter/bin`.
+pub fn resolve_install_dir(install_dir: Option<&Path>, test_dir: Option<&Path>) -> Result<PathBuf> {
+    if let Some(dir) = test_dir {
+        return Ok(dir.to_path_buf());
+    }
+    if let Some(dir) = install_dir {
+        return Ok(expand_path(dir));
+    }
+    match std::env::var(INSTALL_DIR_ENV) {
+        Ok(dir) if !dir.is_empty() => Ok(expand_path(Path::new(&dir))),
+        _ => default_install_dir(),
+    }
+}
+
+/// Expands a leading `~` or `~/` to `$HOME`, for paths the shell never saw.
+///
+/// `~user` forms and paths without a leading tilde are returned unchanged.
+pub fn expand_tilde(path: &Path) -> PathBuf {
+    let Ok(home) = std::env::var("HOME") else {
+        return path.to_path_buf();
+    };
+    match path.strip_prefix("~") {
+        Ok(rest) => PathBuf::from(home).join(rest),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// Expands `$VAR`/`${VAR}` references, then a leading `~`.
+pub fn expand_path(path: &Path) -> PathBuf {
+    match path.to_str() {
+        Some(text) => expand_tilde(Path::new(&expand_env_vars(text))),
+        None => path.to_path_buf(),
+    }
+}
+
+/// Replaces `$VAR` and `${VAR}` with their values; unset variables are kept verbatim.
+pub fn expand_env_vars(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('$') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let (name, len) = variable_reference(after);
+        match std::env::var(name) {
+            Ok(value) if !name.is_empty() => out.push_str(&value),
+            _ => out.push_str(&rest[start..start + 1 + len]),
+        }
+        rest = &after[len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Variable name at the start of `after` (the text following a `$`) and
+/// how many bytes the reference spans.
+fn variable_reference(after: &str) -> (&str, usize) {
+    if let Some(braced) = after.strip_prefix('{') {
+        return match braced.find('}') {
+            Some(end) => (&braced[..end], end + 2),
+            None => ("", 0),
+        };
+    }
+    let end = after
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(after.len());
+    (&after[..end], end)
+}
+
+/// Directories listed in `$PATH`, canonicalized where possible. Build it
+/// once and reuse it across [`is_dir_on_path`] checks.
+pub fn path_dirs() -> HashSet<PathBuf> {
+    std::env::var_os("PATH")
+        .map(|p| std::env::split_paths(&p).map(|d| canonical(&d)).collect())
+        .unwrap_or_default()
+}
+
+/// Whether `dir` is one of `path_dirs`, comparing canonical forms when possible.
+pub fn is_dir_on_path(dir: &Path, path_dirs: &HashSet<PathBuf>) -> bool {
+    path_dirs.contains(&canonical(dir))
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
@@ -0,0 +1,77 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use sw_install_core::{BACKUP_MARKER, IoResultExt, Result, backup_source};
+
+/// Debug-symbol files that may sit beside a binary: `<name>.pdb` files and
+/// `<name>.dSYM` bundles.
+const SIDECAR_SUFFIXES: [&str; 2] = [".pdb", ".dSYM"];
+
+/// Copies an existing `dest_binary` to `<name>.bak-<timestamp>` beside it,
+/// then prunes all but the newest `keep` backups of that binary.
+pub fn backup_existing(dest_binary: &Path, keep: usize) -> Result<Option<PathBuf>> {
+    if !dest_binary.is_file() {
+        return Ok(None);
+    }
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let mut backup = dest_binary.as_os_str().to_owned();
+    backup.push(format!("{BACKUP_MARKER}{millis}"));
+    let backup = PathBuf::from(backup);
+    fs::copy(dest_binary, &backup).io_context("backing up binary to", &backup)?;
+    prune_backups(dest_binary, keep.max(1))?;
+    Ok(Some(backup))
+}
+
+fn prune_backups(dest_binary: &Path, keep: usize) -> Result<()> {
+    let (Some(dir), Some(name)) = (dest_binary.parent(), dest_binary.file_name()) else {
+        return Ok(());
+    };
+    let name = name.to_string_lossy();
+    let mut backups: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .filter(|e| backup_source(&e.file_name().to_string_lossy()) == Some(&name))
+        .map(|e| e.path())
+        .collect();
+    // Timestamps are fixed-width millis, so name order is age order
+    backups.sort();
+    let excess = backups.len().saturating_sub(keep);
+    for old in &backups[..excess] {
+        fs::remove_file(old)?;
+    }
+    Ok(())
+}
+
+/// Removes `binary`'s sidecar debug files and `--backup` copies, returning
+/// what was (or, in a dry run, would be) removed.
+pub fn purge_extras(binary: &Path, dry_run: bool) -> Result<Vec<PathBuf>> {
+    let (Some(dir), Some(name)) = (binary.parent(), binary.file_name()) else {
+        return Ok(Vec::new());
+    };
+    let name = name.to_string_lossy();
+    let mut extras: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            let file = e.file_name().to_string_lossy().into_owned();
+            backup_source(&file) == Some(&name)
+                || SIDECAR_SUFFIXES
+                    .iter()
+                    .any(|s| file == format!("{name}{s}"))
+        })
+        .map(|e| e.path())
+        .collect();
+    extras.sort();
+    for extra in extras.iter().filter(|_| !dry_run) {
+        if extra.is_dir() {
+            fs::remove_dir_all(extra).io_context("removing", extra)?;
+        } else {
+            fs::remove_file(extra).io_context("removing", extra)?;
+        }
+    }
+    Ok(extras)
+}
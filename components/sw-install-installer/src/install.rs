@@ -1,17 +1,25 @@
 // Copyright (c) 2025 Michael A Wright
 // Licensed under the MIT License
 
+use crate::backup::backup_existing;
+use crate::lock::InstallLock;
+use crate::paths::{check_install_dir_is_dir, check_not_directory, check_system_dir};
+use crate::record::{check_name_owner, record_install};
+use crate::temp::copy_atomically;
 use std::fs;
 use std::path::{Path, PathBuf};
-use sw_install_core::{InstallConfig, InstallError, NormalOutput, Result};
-
-#[cfg(unix)]
-use std::os::unix::fs::PermissionsExt;
+use sw_install_core::{
+    DirPolicy, InstallConfig, InstallError, IoResultExt, NormalOutput, Result, StepStatus,
+    default_install_dir,
+};
+use sw_install_manifest::{FileManifestStore, ManifestStore};
 
 pub struct Installer<'a> {
     config: &'a InstallConfig,
     binary_name: String,
     source_binary_path: PathBuf,
+    /// Overrides the install dir's manifest file, e.g. in tests.
+    store: Option<&'a dyn ManifestStore>,
     output: &'a NormalOutput,
 }
 
@@ -26,13 +34,29 @@ impl<'a> Installer<'a> {
             config,
             binary_name,
             source_binary_path,
+            store: None,
             output,
         }
     }
 
+    /// Records the install in `store` instead of the install dir's manifest.
+    pub fn with_manifest_store(mut self, store: &'a dyn ManifestStore) -> Self {
+        self.store = Some(store);
+        self
+    }
+
     pub fn install(&self) -> Result<PathBuf> {
+        self.output.step("prepare", StepStatus::Start);
         let dest_dir = self.prepare_destination()?;
+        self.output.step("prepare", StepStatus::Done);
+        let _lock = self.lock(&dest_dir)?;
+        let file_store = FileManifestStore::new(&dest_dir, self.output);
+        let store = self.store.unwrap_or(&file_store);
+        check_name_owner(store, &self.binary_name, self.config)?;
         let dest_binary = self.copy_and_set_permissions(&dest_dir)?;
+        if !self.config.dry_run {
+            record_install(store, &self.binary_name, self.config)?;
+        }
         self.output.success(&format!(
             "Successfully installed: {} -> {}",
             self.binary_name,
@@ -41,43 +65,87 @@ impl<'a> Installer<'a> {
         Ok(dest_binary)
     }
 
+    /// Whether the installed copy needs no refresh: the source is not
+    /// strictly newer, or it is newer but byte-identical.
+    pub fn is_current(&self) -> Result<bool> {
+        let final_name = self.config.installed_name(&self.binary_name)?;
+        let dest_dir = self.config.destination_dir()?;
+        let dest_binary = self.config.layout.binary_path(&dest_dir, &final_name);
+        let modified = |p: &Path| fs::metadata(p).and_then(|m| m.modified()).ok();
+        let (Some(installed), Some(source)) =
+            (modified(&dest_binary), modified(&self.source_binary_path))
+        else {
+            return Ok(false);
+        };
+        Ok(source <= installed || fs::read(&dest_binary)? == fs::read(&self.source_binary_path)?)
+    }
+
     fn prepare_destination(&self) -> Result<PathBuf> {
         self.output.info("[1/3] Creating destination directory...");
         let dest_dir = self.config.destination_dir()?;
-        if self.config.test_dir.is_none()
-            && !self.config.dry_run
+        check_system_dir(&dest_dir, self.config.force, self.output)?;
+        check_install_dir_is_dir(&dest_dir)?;
+        // Only the default dir insists on --setup-install-dir; a dir the
+        // user chose explicitly is created in full
+        if !self.config.dry_run
+            && self.config.dir_policy == DirPolicy::RequireSetup
+            && default_install_dir().is_ok_and(|d| d == dest_dir)
             && let Some(parent) = dest_dir.parent()
             && !parent.exists()
         {
             return Err(InstallError::InstallDirNotFound(dest_dir.clone()));
         }
         if !self.config.dry_run {
-            fs::create_dir_all(&dest_dir)?;
+            fs::create_dir_all(&dest_dir).io_context("creating directory", &dest_dir)?;
         }
         self.output
             .info(&format!("Destination: {}", dest_dir.display()));
         Ok(dest_dir)
     }
 
+    fn lock(&self, dest_dir: &Path) -> Result<Option<InstallLock>> {
+        if self.config.dry_run {
+            return Ok(None);
+        }
+        InstallLock::acquire(dest_dir).map(Some)
+    }
+
     fn copy_and_set_permissions(&self, dest_dir: &Path) -> Result<PathBuf> {
         self.output.info("[2/3] Copying binary...");
-        let final_name = self.config.rename.as_deref().unwrap_or(&self.binary_name);
-        let dest_binary = dest_dir.join(final_name);
+        self.output.step("copy", StepStatus::Start);
+        let final_name = self.config.installed_name(&self.binary_name)?;
+        let dest_binary = self.config.layout.binary_path(dest_dir, &final_name);
+        check_not_directory(&dest_binary)?;
         if !self.config.dry_run {
-            // Remove existing binary first to avoid "text file busy" on Linux
-            // when overwriting a running executable
-            let _ = fs::remove_file(&dest_binary);
-            fs::copy(&self.source_binary_path, &dest_binary)?;
+            if let Some(parent) = dest_binary.parent() {
+                fs::create_dir_all(parent).io_context("creating directory", parent)?;
+            }
+            if self.config.backup
+                && let Some(backup) = backup_existing(&dest_binary, self.config.keep_backups)?
+            {
+                self.output
+                    .info(&format!("Backed up to: {}", backup.display()));
+            }
+            // Renaming over the destination also avoids "text file busy" on
+            // Linux when replacing a running executable
+            copy_atomically(
+                &self.source_binary_path,
+                &dest_binary,
+                self.config.set_executable,
+                self.output,
+            )?;
         }
         self.output
             .info(&format!("Copied to: {}", dest_binary.display()));
+        self.output.step("copy", StepStatus::Done);
+        // copy_atomically already set the mode before the rename
         self.output.info("[3/3] Setting executable permissions...");
-        #[cfg(unix)]
-        if !self.config.dry_run {
-            let mut perms = fs::metadata(&dest_binary)?.permissions();
-            perms.set_mode(0o755);
-            fs::set_permissions(&dest_binary, perms)?;
+        self.output.step("permissions", StepStatus::Start);
+        if !self.config.set_executable {
+            self.output
+                .info("--no-executable-bit: leaving permissions as copied");
         }
+        self.output.step("permissions", StepStatus::Done);
         Ok(dest_binary)
     }
 }
@@ -3,9 +3,15 @@
 
 //! Install and uninstall operations for sw-install.
 
+mod backup;
 mod install;
+mod lock;
 mod paths;
+mod record;
+mod temp;
 mod uninstall;
 
 pub use install::Installer;
+pub use lock::{InstallLock, LOCK_FILE_NAME};
+pub use temp::{TempFileGuard, cleanup_registered};
 pub use uninstall::Uninstaller;
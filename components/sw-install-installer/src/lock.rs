@@ -0,0 +1,34 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use std::fs::{File, OpenOptions};
+use std::path::Path;
+use sw_install_core::Result;
+
+pub const LOCK_FILE_NAME: &str = ".sw-install.lock";
+
+/// Advisory lock on an installation directory, held until dropped.
+///
+/// Serializes concurrent sw-install runs that modify the same directory.
+pub struct InstallLock {
+    file: File,
+}
+
+impl InstallLock {
+    /// Blocks until the lock on `dir` is acquired. `dir` must already exist.
+    pub fn acquire(dir: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(dir.join(LOCK_FILE_NAME))?;
+        file.lock()?;
+        Ok(Self { file })
+    }
+}
+
+impl Drop for InstallLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
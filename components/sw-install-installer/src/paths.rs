@@ -2,17 +2,10 @@
 // Licensed under the MIT License
 
 use std::path::{Path, PathBuf};
-use sw_install_core::{InstallError, Result};
+use sw_install_core::{InstallError, NormalOutput, Result};
 
-pub fn get_dest_dir(test_dir: &Option<PathBuf>) -> Result<PathBuf> {
-    match test_dir {
-        Some(dir) => Ok(dir.clone()),
-        None => {
-            let home = std::env::var("HOME").map_err(|_| InstallError::HomeNotFound)?;
-            Ok(PathBuf::from(home).join(".local/softwarewrighter/bin"))
-        }
-    }
-}
+/// Directories whose tools sw-install must not shadow without `--force`.
+const SYSTEM_BIN_DIRS: [&str; 4] = ["/usr/bin", "/bin", "/usr/local/bin", "/sbin"];
 
 pub fn validate_binary_exists(path: &Path, name: &str, check_parent: bool) -> Result<PathBuf> {
     if check_parent
@@ -24,5 +17,42 @@ pub fn validate_binary_exists(path: &Path, name: &str, check_parent: bool) -> Re
     if !path.exists() {
         return Err(InstallError::BinaryNotInstalled(name.to_string()));
     }
+    check_not_directory(path)?;
     Ok(path.to_path_buf())
 }
+
+/// A directory where a binary belongs is user error or a leftover; copy
+/// and remove_file would otherwise fail with a bare IO error.
+pub fn check_not_directory(path: &Path) -> Result<()> {
+    if path.is_dir() {
+        return Err(InstallError::DestinationIsDirectory(path.to_path_buf()));
+    }
+    Ok(())
+}
+
+/// Rejects an install dir that is, or sits under, an existing non-directory,
+/// which `create_dir_all` would otherwise report as a bare IO error.
+pub fn check_install_dir_is_dir(dest_dir: &Path) -> Result<()> {
+    match dest_dir.ancestors().find(|p| p.exists()) {
+        Some(existing) if !existing.is_dir() => Err(InstallError::InstallDirNotADirectory(
+            existing.to_path_buf(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Refuses well-known system directories unless `force` is set, in which
+/// case it only warns.
+pub fn check_system_dir(dest_dir: &Path, force: bool, output: &NormalOutput) -> Result<()> {
+    if !SYSTEM_BIN_DIRS.iter().any(|d| dest_dir == Path::new(d)) {
+        return Ok(());
+    }
+    if !force {
+        return Err(InstallError::SystemInstallDir(dest_dir.to_path_buf()));
+    }
+    output.warn(&format!(
+        "Installing into system directory {}; binaries here can shadow system tools",
+        dest_dir.display()
+    ));
+    Ok(())
+}
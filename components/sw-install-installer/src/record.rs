@@ -0,0 +1,53 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+use sw_install_core::{InstallConfig, InstallError, Result};
+use sw_install_manifest::{ManifestEntry, ManifestStore};
+
+/// Records cargo binary `binary` as installed from the configured project.
+pub fn record_install(
+    store: &dyn ManifestStore,
+    binary: &str,
+    config: &InstallConfig,
+) -> Result<()> {
+    let source = project_source(config);
+    let name = config.installed_name(binary)?;
+    let mut entry = ManifestEntry::new(&name, Some(source), SystemTime::now());
+    entry.debug = config.use_debug;
+    entry.binary = (name != binary).then(|| binary.to_string());
+    store.record(entry)
+}
+
+/// Drops `name` from the store, if it tracks it.
+pub fn forget_install(store: &dyn ManifestStore, name: &str) -> Result<()> {
+    store.remove(name)?;
+    Ok(())
+}
+
+/// Refuses to replace a binary the store records from another project,
+/// unless forced. Keyed off the installed name, so `--rename` onto another
+/// project's binary is caught as well.
+pub fn check_name_owner(
+    store: &dyn ManifestStore,
+    binary: &str,
+    config: &InstallConfig,
+) -> Result<()> {
+    let name = config.installed_name(binary)?;
+    // Loading already sets a corrupt manifest aside, so an error here is a
+    // real read failure; skipping the check would silently drop protection
+    let manifest = store.load()?;
+    match manifest.get(&name).and_then(|e| e.source.clone()) {
+        Some(owner) if !config.force && owner != project_source(config) => {
+            Err(InstallError::NameTaken(name, owner))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn project_source(config: &InstallConfig) -> PathBuf {
+    let project_path = &config.project_path;
+    fs::canonicalize(project_path).unwrap_or_else(|_| project_path.to_path_buf())
+}
@@ -0,0 +1,262 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use std::ffi::{CString, c_char};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::ptr::null_mut;
+use std::sync::atomic::{AtomicPtr, Ordering};
+use sw_install_core::{IoResultExt, NormalOutput, Result};
+
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+
+/// Path of the in-progress temp file, readable from a signal handler.
+///
+/// sw-install copies one binary at a time, so a single slot suffices.
+/// Paths replaced in the slot are leaked rather than freed: a handler on
+/// any thread may have loaded the pointer just before the store, and one
+/// short string per installed binary is a cheap price for never freeing it
+/// under the handler.
+static TEMP_PATH: AtomicPtr<c_char> = AtomicPtr::new(null_mut());
+
+/// Copies `source` to a dotted temp file beside `dest`, marks it executable
+/// when `executable` is set, and renames it into place so `dest` is never
+/// observed half-written. The temp file shares `dest`'s directory, so the
+/// rename stays on one filesystem.
+pub fn copy_atomically(
+    source: &Path,
+    dest: &Path,
+    executable: bool,
+    output: &NormalOutput,
+) -> Result<()> {
+    let name = dest.file_name().unwrap_or_default().to_string_lossy();
+    let temp = dest.with_file_name(format!(".{name}.tmp"));
+    let _guard = TempFileGuard::register(&temp);
+    fs::copy(source, &temp).io_context("copying binary to", dest)?;
+    #[cfg(unix)]
+    if executable {
+        let mut perms = fs::metadata(&temp)
+            .io_context("reading metadata of", &temp)?
+            .permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&temp, perms).io_context("setting permissions on", &temp)?;
+    }
+    move_into_place(&temp, dest, |from, to| fs::rename(from, to), output)
+}
+
+/// Renames `temp` over `dest`, falling back to a plain (non-atomic) copy
+/// when `rename` reports the two are on different filesystems.
+fn move_into_place(
+    temp: &Path,
+    dest: &Path,
+    rename: impl FnOnce(&Path, &Path) -> io::Result<()>,
+    output: &NormalOutput,
+) -> Result<()> {
+    match rename(temp, dest) {
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {
+            output.warn(&format!(
+                "{} is on another filesystem; copying without an atomic rename",
+                dest.display()
+            ));
+            fs::copy(temp, dest).io_context("copying binary to", dest)?;
+            Ok(())
+        }
+        result => result.io_context("moving binary into place at", dest),
+    }
+}
+
+/// Removes a temp file on SIGINT/SIGTERM, or on drop if it still exists.
+pub struct TempFileGuard {
+    path: PathBuf,
+}
+
+impl TempFileGuard {
+    /// Registers `path` for cleanup and installs the interrupt handlers.
+    pub fn register(path: &Path) -> Self {
+        let raw =
+            CString::new(path.as_os_str().as_encoded_bytes()).map_or(null_mut(), CString::into_raw);
+        TEMP_PATH.store(raw, Ordering::SeqCst);
+        #[cfg(unix)]
+        signals::install();
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+}
+
+impl Drop for TempFileGuard {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        signals::restore();
+        TEMP_PATH.store(null_mut(), Ordering::SeqCst);
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Deletes the registered temp file, if any. Safe to call from a signal handler.
+pub fn cleanup_registered() {
+    let raw = TEMP_PATH.load(Ordering::SeqCst);
+    if raw.is_null() {
+        return;
+    }
+    #[cfg(unix)]
+    // SAFETY: `raw` came from CString::into_raw and is never freed.
+    unsafe {
+        libc::unlink(raw);
+    }
+    #[cfg(not(unix))]
+    // SAFETY: as above.
+    if let Ok(path) = unsafe { std::ffi::CStr::from_ptr(raw) }.to_str() {
+        let _ = fs::remove_file(path);
+    }
+}
+
+#[cfg(unix)]
+mod signals {
+    use std::ptr::null_mut;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    const SIGNALS: [libc::c_int; 2] = [libc::SIGINT, libc::SIGTERM];
+
+    /// Dispositions in place before `install`, reinstated by `restore` and
+    /// by the handler before it passes the signal on.
+    // SAFETY: all-zero is a valid sigaction (SIG_DFL, empty mask, no flags).
+    static mut PREVIOUS: [libc::sigaction; 2] = unsafe { std::mem::zeroed() };
+
+    /// Whether our handler is installed, so a second `install` cannot save
+    /// it as the previous disposition.
+    static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+    extern "C" fn on_signal(signal: libc::c_int) {
+        super::cleanup_registered();
+        let Some(i) = SIGNALS.iter().position(|&s| s == signal) else {
+            return;
+        };
+        // SAFETY: sigaction and raise are async-signal-safe, and PREVIOUS[i]
+        // was filled before this handler was installed. The raised signal
+        // is delivered to the previous disposition once this returns.
+        unsafe {
+            libc::sigaction(signal, &raw const PREVIOUS[i], null_mut());
+            libc::raise(signal);
+        }
+    }
+
+    pub fn install() {
+        if INSTALLED.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        // SAFETY: as for PREVIOUS.
+        let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+        action.sa_sigaction = on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        for (i, signal) in SIGNALS.into_iter().enumerate() {
+            // SAFETY: on_signal only performs async-signal-safe calls, and
+            // PREVIOUS is written only here, while INSTALLED is held.
+            unsafe { libc::sigaction(signal, &action, &raw mut PREVIOUS[i]) };
+        }
+    }
+
+    pub fn restore() {
+        if !INSTALLED.swap(false, Ordering::SeqCst) {
+            return;
+        }
+        for (i, signal) in SIGNALS.into_iter().enumerate() {
+            // SAFETY: PREVIOUS[i] holds the disposition saved by install.
+            unsafe { libc::sigaction(signal, &raw const PREVIOUS[i], null_mut()) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use sw_install_core::CapturedOutput;
+    use tempfile::TempDir;
+
+    #[test]
+    #[serial]
+    fn copy_replaces_dest_and_leaves_no_temp_file() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("source");
+        let dest = dir.path().join("app");
+        fs::write(&source, "new binary").unwrap();
+        fs::write(&dest, "old binary").unwrap();
+
+        copy_atomically(&source, &dest, true, &NormalOutput::default()).unwrap();
+
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "new binary");
+        assert!(!dir.path().join(".app.tmp").exists());
+    }
+
+    #[test]
+    fn cross_device_rename_falls_back_to_copy() {
+        let dir = TempDir::new().unwrap();
+        let temp = dir.path().join(".app.tmp");
+        let dest = dir.path().join("app");
+        fs::write(&temp, "new binary").unwrap();
+        let captured = CapturedOutput::default();
+        let output = NormalOutput::default().with_handler(captured.clone());
+
+        let cross_device = |_: &Path, _: &Path| Err(io::ErrorKind::CrossesDevices.into());
+        move_into_place(&temp, &dest, cross_device, &output).unwrap();
+
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "new binary");
+        assert!(captured.lines()[0].contains("another filesystem"));
+    }
+
+    #[test]
+    fn other_rename_errors_are_reported() {
+        let dir = TempDir::new().unwrap();
+        let temp = dir.path().join(".app.tmp");
+        let dest = dir.path().join("app");
+        fs::write(&temp, "new binary").unwrap();
+
+        let denied = |_: &Path, _: &Path| Err(io::ErrorKind::PermissionDenied.into());
+        let output = NormalOutput::default();
+        assert!(move_into_place(&temp, &dest, denied, &output).is_err());
+        assert!(!dest.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial]
+    fn guard_restores_the_previous_signal_handler() {
+        extern "C" fn embedder_handler(_: libc::c_int) {}
+        let handler = embedder_handler as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        let current = || {
+            // SAFETY: querying a disposition with a null new action is sound
+            let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+            unsafe { libc::sigaction(libc::SIGTERM, std::ptr::null(), &mut action) };
+            action.sa_sigaction
+        };
+        // SAFETY: embedder_handler does nothing
+        let before = unsafe { libc::signal(libc::SIGTERM, handler) };
+
+        let dir = TempDir::new().unwrap();
+        let guard = TempFileGuard::register(&dir.path().join(".app.tmp"));
+        assert_ne!(current(), handler);
+        drop(guard);
+        assert_eq!(current(), handler);
+
+        // SAFETY: reinstating the disposition found at the start
+        unsafe { libc::signal(libc::SIGTERM, before) };
+    }
+
+    #[test]
+    #[serial]
+    fn rename_errors_are_reported_and_temp_file_removed() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("source");
+        fs::write(&source, "new binary").unwrap();
+        // A file cannot be renamed over a non-empty directory
+        let dest = dir.path().join("app");
+        fs::create_dir(&dest).unwrap();
+        fs::write(dest.join("keep"), "").unwrap();
+
+        assert!(copy_atomically(&source, &dest, false, &NormalOutput::default()).is_err());
+        assert!(dest.join("keep").exists());
+        assert!(!dir.path().join(".app.tmp").exists());
+    }
+}
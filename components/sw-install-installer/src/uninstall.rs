@@ -1,15 +1,25 @@
 // Copyright (c) 2025 Michael A Wright
 // Licensed under the MIT License
 
-use crate::paths::{get_dest_dir, validate_binary_exists};
+use crate::backup::purge_extras;
+use crate::lock::InstallLock;
+use crate::paths::validate_binary_exists;
+use crate::record::forget_install;
 use std::fs;
-use std::path::PathBuf;
-use sw_install_core::{NormalOutput, Result};
+use std::path::{Path, PathBuf};
+use sw_install_core::{
+    IoResultExt, NormalOutput, Result, StepStatus, find_installed_binary, resolve_install_dir,
+};
+use sw_install_manifest::{FileManifestStore, ManifestStore};
 
 pub struct Uninstaller<'a> {
     binary_name: String,
     dry_run: bool,
     test_dir: Option<PathBuf>,
+    install_dir: Option<PathBuf>,
+    purge: bool,
+    /// Overrides the install dir's manifest file, e.g. in tests.
+    store: Option<&'a dyn ManifestStore>,
     output: &'a NormalOutput,
 }
 
@@ -24,33 +34,76 @@ impl<'a> Uninstaller<'a> {
             binary_name: name,
             dry_run,
             test_dir,
+            install_dir: None,
+            purge: false,
+            store: None,
             output: out,
         }
     }
 
+    pub fn with_install_dir(mut self, install_dir: Option<PathBuf>) -> Self {
+        self.install_dir = install_dir;
+        self
+    }
+
+    /// Also removes sidecar debug files and backups of the binary.
+    pub fn with_purge(mut self, purge: bool) -> Self {
+        self.purge = purge;
+        self
+    }
+
+    /// Forgets the binary in `store` instead of the install dir's manifest.
+    pub fn with_manifest_store(mut self, store: &'a dyn ManifestStore) -> Self {
+        self.store = Some(store);
+        self
+    }
+
     pub fn uninstall(&self) -> Result<()> {
-        let binary_path = self.locate_and_validate()?;
-        self.remove_binary(&binary_path)?;
+        let dest_dir = resolve_install_dir(self.install_dir.as_deref(), self.test_dir.as_deref())?;
+        let binary_path = self.locate_and_validate(&dest_dir)?;
+        self.remove_binary(&dest_dir, &binary_path)?;
         self.output
             .success(&format!("Successfully uninstalled: {}", self.binary_name));
         Ok(())
     }
 
-    fn locate_and_validate(&self) -> Result<PathBuf> {
+    fn locate_and_validate(&self, dest_dir: &Path) -> Result<PathBuf> {
         self.output.info("[1/2] Locating binary...");
-        let dest_dir = get_dest_dir(&self.test_dir)?;
-        let binary_path = dest_dir.join(&self.binary_name);
+        self.output.step("locate", StepStatus::Start);
+        let binary_path = find_installed_binary(dest_dir, &self.binary_name)
+            .unwrap_or_else(|| dest_dir.join(&self.binary_name));
         self.output
             .info(&format!("Binary path: {}", binary_path.display()));
+        self.output.step("locate", StepStatus::Done);
         self.output.info("[2/2] Validating binary exists...");
-        validate_binary_exists(&binary_path, &self.binary_name, self.test_dir.is_none())
+        self.output.step("validate", StepStatus::Start);
+        let binary_path =
+            validate_binary_exists(&binary_path, &self.binary_name, self.test_dir.is_none())?;
+        self.output.step("validate", StepStatus::Done);
+        Ok(binary_path)
     }
 
-    fn remove_binary(&self, binary_path: &PathBuf) -> Result<()> {
+    fn remove_binary(&self, dest_dir: &Path, binary_path: &Path) -> Result<()> {
         self.output.info("Removing binary...");
+        self.output.step("remove", StepStatus::Start);
+        if self.purge {
+            for extra in purge_extras(binary_path, self.dry_run)? {
+                self.output.info(&format!("Removing: {}", extra.display()));
+            }
+        }
         if !self.dry_run {
-            fs::remove_file(binary_path)?;
+            let _lock = InstallLock::acquire(dest_dir)?;
+            fs::remove_file(binary_path).io_context("removing", binary_path)?;
+            // A nested layout leaves `<dest>/<name>/`; drop it once empty
+            if let Some(parent) = binary_path.parent()
+                && parent != dest_dir
+            {
+                let _ = fs::remove_dir(parent);
+            }
+            let file_store = FileManifestStore::new(dest_dir, self.output);
+            forget_install(self.store.unwrap_or(&file_store), &self.binary_name)?;
         }
+        self.output.step("remove", StepStatus::Done);
         Ok(())
     }
 }
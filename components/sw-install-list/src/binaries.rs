@@ -1,33 +1,296 @@
 // Copyright (c) 2025 Michael A Wright
 // Licensed under the MIT License
 
+use crate::format::ListOptions;
+use crate::inspect::{OwnerNames, binary_arch, is_foreign_owner};
+use crate::pattern::NamePattern;
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
-use std::time::SystemTime;
-use sw_install_core::{InstallError, Result};
-
-pub fn get_bin_dir(test_dir: &Option<PathBuf>) -> Result<PathBuf> {
-    let bin_dir = match test_dir {
-        Some(dir) => dir.clone(),
-        None => {
-            let home = std::env::var("HOME").map_err(|_| InstallError::HomeNotFound)?;
-            PathBuf::from(home).join(".local/softwarewrighter/bin")
-        }
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sw_install_core::{
+    InstallError, NormalOutput, Result, installed_archives, is_dir_on_path, path_dirs,
+    scan_installed,
+};
+use sw_install_manifest::{Manifest, ManifestStore};
+
+/// An installed binary as seen in the installation directory.
+#[derive(Debug, Clone)]
+pub struct BinaryInfo {
+    pub name: String,
+    pub modified: SystemTime,
+    /// When the manifest records the binary as installed, falling back to
+    /// `modified` for binaries it has no record of.
+    pub installed_at: SystemTime,
+    pub size: u64,
+    /// Whether the manifest records a debug build.
+    pub debug: bool,
+    /// `debug` or `release` as the manifest records it; `None` for binaries
+    /// sw-install has no record of.
+    pub build_type: Option<&'static str>,
+    /// Cargo target triple the manifest records, for cross-compiled installs.
+    pub target_triple: Option<String>,
+    /// Whether the binary's directory is on the current `$PATH`.
+    pub on_path: bool,
+    /// User owning the file (numeric UID when unresolvable). Resolved only
+    /// when the listing shows owners or another user owns the file; always
+    /// `None` off Unix.
+    pub owner: Option<String>,
+    /// Whether a user other than the one listing owns the file.
+    pub foreign_owner: bool,
+    /// CPU the binary targets (`x86_64`, `aarch64`, ...), or `unknown`.
+    /// Read from the file header only when the listing shows it.
+    pub arch: Option<String>,
+    /// Project the manifest records the binary as installed from.
+    pub source: Option<PathBuf>,
+    pub path: PathBuf,
+    /// Whether the entry in the install dir is a symlink.
+    pub is_symlink: bool,
+    /// Absolute, fully resolved target of a symlinked entry; `None` for
+    /// regular files and for links whose target is gone.
+    pub link_target: Option<PathBuf>,
+    /// Other names holding byte-identical copies, under `--dedupe-by checksum`.
+    pub aliases: Vec<String>,
+}
+
+/// One listing of an installation directory.
+#[derive(Debug, Default)]
+pub struct Listing {
+    pub binaries: Vec<BinaryInfo>,
+    /// Entries that look installed but could not be read, with the reason.
+    pub unreadable: Vec<(PathBuf, io::Error)>,
+}
+
+/// Checks `bin_dir` exists; only the install dir gets the setup hint, not
+/// an arbitrary directory passed with `--dir`.
+pub fn get_bin_dir(bin_dir: PathBuf, explicit: bool) -> Result<PathBuf> {
+    if bin_dir.exists() {
+        Ok(bin_dir)
+    } else if explicit {
+        Err(InstallError::ListDirNotFound(bin_dir))
+    } else {
+        Err(InstallError::InstallDirNotFound(bin_dir))
+    }
+}
+
+/// Which entries a listing keeps, checked ahead of each entry's costlier
+/// lookups: names before any stat, times before owners and headers.
+#[derive(Debug, Default)]
+pub struct EntryFilter<'a> {
+    pub grep: Option<&'a NamePattern>,
+    /// Keeps only binaries last modified before this.
+    pub modified_before: Option<SystemTime>,
+    /// Keeps only binaries installed at or after this.
+    pub installed_after: Option<SystemTime>,
+    /// Keeps only binaries installed strictly before this.
+    pub installed_before: Option<SystemTime>,
+}
+
+impl EntryFilter<'_> {
+    fn keeps_name(&self, name: &str) -> bool {
+        self.grep.is_none_or(|grep| grep.is_match(name))
+    }
+
+    fn keeps_times(&self, modified: SystemTime, installed_at: SystemTime) -> bool {
+        self.modified_before.is_none_or(|cutoff| modified < cutoff)
+            && self
+                .installed_after
+                .is_none_or(|after| installed_at >= after)
+            && self
+                .installed_before
+                .is_none_or(|before| installed_at < before)
+    }
+}
+
+/// Installed binaries in `bin_dir` that `filter` keeps, plus `--backup`
+/// archives when asked, from a single pass over the directory.
+pub fn collect_binaries(
+    bin_dir: &Path,
+    options: &ListOptions,
+    filter: &EntryFilter,
+    store: &dyn ManifestStore,
+    output: &NormalOutput,
+) -> Result<Listing> {
+    let scan = Scan {
+        // A corrupt manifest loads as empty; only real read errors get here
+        manifest: store.load()?,
+        // Canonicalized once; each entry then canonicalizes only its own dir
+        path_dirs: path_dirs(),
+        owners: OwnerNames::default(),
+        details: options.shows_details(),
+        output,
     };
-    if !bin_dir.exists() {
-        return Err(InstallError::InstallDirNotFound(bin_dir));
+    let mut found = scan_installed(bin_dir)?;
+    if options.archives {
+        found.binaries.extend(installed_archives(bin_dir)?);
     }
-    Ok(bin_dir)
+    Ok(Listing {
+        binaries: found
+            .binaries
+            .into_iter()
+            .filter(|(name, _)| filter.keeps_name(name))
+            .filter_map(|(name, path)| scan.binary_info(name, &path, filter))
+            .collect(),
+        unreadable: found.unreadable,
+    })
 }
 
-pub fn collect_binaries(bin_dir: &PathBuf) -> Result<Vec<(String, SystemTime)>> {
-    Ok(fs::read_dir(bin_dir)?
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().is_file())
-        .filter_map(|e| {
-            let name = e.file_name().to_str()?.to_string();
-            let time = fs::metadata(e.path()).and_then(|m| m.modified()).ok()?;
-            Some((name, time))
+/// State shared by every entry of one listing, built once per listing.
+struct Scan<'a> {
+    manifest: Manifest,
+    path_dirs: HashSet<PathBuf>,
+    owners: OwnerNames,
+    /// Whether the output shows owners and architectures, which need a
+    /// user name lookup and a header read.
+    details: bool,
+    output: &'a NormalOutput,
+}
+
+impl Scan<'_> {
+    fn binary_info(&self, name: String, path: &Path, filter: &EntryFilter) -> Option<BinaryInfo> {
+        let metadata = fs::metadata(path).ok()?;
+        let entry = self.manifest.get(&name);
+        let modified = modified_or_epoch(metadata.modified(), path, self.output);
+        let installed_at = entry.filter(|m| m.installed_at > 0).map_or(modified, |m| {
+            UNIX_EPOCH + Duration::from_secs(m.installed_at)
+        });
+        if !filter.keeps_times(modified, installed_at) {
+            return None;
+        }
+        let foreign_owner = is_foreign_owner(&metadata);
+        Some(BinaryInfo {
+            modified,
+            installed_at,
+            size: metadata.len(),
+            debug: entry.is_some_and(|m| m.debug),
+            build_type: entry.map(|m| if m.debug { "debug" } else { "release" }),
+            target_triple: entry.and_then(|m| m.target_triple.clone()),
+            on_path: path
+                .parent()
+                .is_some_and(|dir| is_dir_on_path(dir, &self.path_dirs)),
+            // Foreign owners are named in a warning whatever the format
+            owner: (self.details || foreign_owner)
+                .then(|| self.owners.owner(&metadata))
+                .flatten(),
+            foreign_owner,
+            arch: self.details.then(|| binary_arch(path)),
+            source: entry.and_then(|m| m.source.clone()),
+            path: path.to_path_buf(),
+            is_symlink: is_symlink(path),
+            link_target: is_symlink(path)
+                .then(|| fs::canonicalize(path).ok())
+                .flatten(),
+            aliases: Vec::new(),
+            name,
         })
-        .collect())
+    }
+}
+
+fn is_symlink(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink())
+}
+
+/// Heading for binaries the manifest has no source for.
+pub const UNKNOWN_SOURCE: &str = "(unknown source)";
+
+/// Splits `bins` by recorded source project, keeping their order within
+/// each group. Groups follow source path order, with unknown sources last.
+pub fn group_by_source(bins: &[BinaryInfo]) -> Vec<(Option<&Path>, Vec<BinaryInfo>)> {
+    let mut groups: BTreeMap<(bool, Option<&Path>), Vec<BinaryInfo>> = BTreeMap::new();
+    for bin in bins {
+        let source = bin.source.as_deref();
+        groups
+            .entry((source.is_none(), source))
+            .or_default()
+            .push(bin.clone());
+    }
+    groups
+        .into_iter()
+        .map(|((_, source), group)| (source, group))
+        .collect()
+}
+
+/// Folds byte-identical binaries into the first of them in `bins` order,
+/// recording the others as its aliases.
+pub fn dedupe_by_checksum(bins: Vec<BinaryInfo>) -> Vec<BinaryInfo> {
+    let mut kept: Vec<(Option<(u64, u64)>, BinaryInfo)> = Vec::new();
+    for bin in bins {
+        let key = checksum(&bin.path).map(|sum| (bin.size, sum));
+        match kept.iter_mut().find(|(k, _)| key.is_some() && *k == key) {
+            Some((_, first)) => first.aliases.push(bin.name),
+            None => kept.push((key, bin)),
+        }
+    }
+    kept.into_iter().map(|(_, bin)| bin).collect()
+}
+
+/// 64-bit SipHash of the file contents; with the size, collisions between
+/// distinct binaries are not a practical concern for grouping.
+fn checksum(path: &Path) -> Option<u64> {
+    let bytes = fs::read(path).ok()?;
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// Some filesystems cannot report mtimes; such a binary still lists, as
+/// the oldest entry, instead of failing the whole listing.
+fn modified_or_epoch(
+    modified: io::Result<SystemTime>,
+    path: &Path,
+    output: &NormalOutput,
+) -> SystemTime {
+    modified.unwrap_or_else(|e| {
+        output.warn(&format!(
+            "cannot read modification time of {}: {e}",
+            path.display()
+        ));
+        SystemTime::UNIX_EPOCH
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sw_install_core::CapturedOutput;
+
+    #[test]
+    fn unreadable_mtime_falls_back_to_epoch() {
+        let unsupported = Err(io::Error::from(io::ErrorKind::Unsupported));
+        let captured = CapturedOutput::default();
+        let output = NormalOutput::default().with_handler(captured.clone());
+        let modified = modified_or_epoch(unsupported, Path::new("odd"), &output);
+        assert_eq!(modified, SystemTime::UNIX_EPOCH);
+        let lines = captured.lines();
+        assert_eq!(lines.len(), 1, "{lines:?}");
+        assert!(lines[0].starts_with("Warning: cannot read modification time of odd"));
+    }
+
+    #[test]
+    fn filter_time_bounds_are_half_open() {
+        let at = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+        let filter = EntryFilter {
+            installed_after: Some(at),
+            installed_before: Some(at + Duration::from_secs(1)),
+            ..EntryFilter::default()
+        };
+        assert!(filter.keeps_times(at, at));
+        assert!(!filter.keeps_times(at, at + Duration::from_secs(1)));
+        assert!(!filter.keeps_times(at, at - Duration::from_secs(1)));
+        let stale = EntryFilter {
+            modified_before: Some(at),
+            ..EntryFilter::default()
+        };
+        assert!(!stale.keeps_times(at, at));
+        assert!(stale.keeps_times(at - Duration::from_secs(1), at));
+    }
+
+    #[test]
+    fn readable_mtime_is_kept() {
+        let now = SystemTime::now();
+        let output = NormalOutput::default();
+        assert_eq!(modified_or_epoch(Ok(now), Path::new("app"), &output), now);
+    }
 }
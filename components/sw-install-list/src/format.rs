@@ -0,0 +1,91 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+/// Presentation options for the list command.
+#[derive(Debug, Clone)]
+pub struct ListOptions {
+    pub format: ListFormat,
+    /// Append a count and combined size summary.
+    pub total: bool,
+    /// Print bare names only, for piping into other commands.
+    pub names_only: bool,
+    /// Show size and build details alongside each name.
+    pub long: bool,
+    /// Print nothing rather than "No binaries installed" for an empty dir.
+    pub quiet: bool,
+    /// Include `--backup`/`--keep-old-versions` archives.
+    pub archives: bool,
+    /// Collapse byte-identical binaries into one entry with aliases.
+    pub dedupe: bool,
+    /// Print text output under one heading per source project.
+    pub group_by_source: bool,
+    /// Indent JSON output for reading instead of one compact line.
+    pub pretty: bool,
+    /// Emit JSON as the bare entries array, without the wrapping object.
+    pub json_array: bool,
+}
+
+impl Default for ListOptions {
+    fn default() -> Self {
+        Self {
+            format: ListFormat::Text,
+            total: false,
+            names_only: false,
+            long: false,
+            quiet: false,
+            archives: false,
+            dedupe: false,
+            group_by_source: false,
+            pretty: false,
+            json_array: false,
+        }
+    }
+}
+
+impl ListOptions {
+    /// Whether the output shows per-file details such as the owner, which
+    /// cost extra lookups: long text listings and JSON.
+    pub fn shows_details(&self) -> bool {
+        match self.format {
+            ListFormat::Json => true,
+            ListFormat::Text => self.long && !self.names_only,
+            ListFormat::Csv => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InvalidListFormat(pub String);
+
+impl std::fmt::Display for InvalidListFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid format '{}'. Valid options: text, json, csv",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for InvalidListFormat {}
+
+impl FromStr for ListFormat {
+    type Err = InvalidListFormat;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(ListFormat::Text),
+            "json" => Ok(ListFormat::Json),
+            "csv" => Ok(ListFormat::Csv),
+            _ => Err(InvalidListFormat(s.to_string())),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+//! Facts about an installed file beyond its size and mtime: who owns it
+//! (Unix only; other platforms report no owner) and which CPU it targets.
+
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::fs::{File, Metadata};
+use std::io::Read;
+use std::path::Path;
+
+/// Architecture shown for files that are not a recognized executable.
+pub const UNKNOWN_ARCH: &str = "unknown";
+
+/// Resolves file owners to user names, reading `/etc/passwd` at most once
+/// per listing and only when an owner is actually asked for.
+#[derive(Debug, Default)]
+pub struct OwnerNames {
+    #[cfg_attr(not(unix), allow(dead_code))]
+    names: OnceCell<HashMap<u32, String>>,
+}
+
+impl OwnerNames {
+    /// Username owning the file, or its numeric UID when the name is unknown.
+    #[cfg(unix)]
+    pub fn owner(&self, metadata: &Metadata) -> Option<String> {
+        use std::os::unix::fs::MetadataExt;
+        let uid = metadata.uid();
+        let names = self.names.get_or_init(read_passwd);
+        Some(names.get(&uid).cloned().unwrap_or_else(|| uid.to_string()))
+    }
+
+    #[cfg(not(unix))]
+    pub fn owner(&self, _metadata: &Metadata) -> Option<String> {
+        None
+    }
+}
+
+/// Whether another user (root included) owns the file, which is suspicious
+/// in a personal install dir.
+#[cfg(unix)]
+pub fn is_foreign_owner(metadata: &Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    // SAFETY: getuid has no preconditions and cannot fail
+    metadata.uid() != unsafe { libc::getuid() }
+}
+
+#[cfg(not(unix))]
+pub fn is_foreign_owner(_metadata: &Metadata) -> bool {
+    false
+}
+
+/// UID to name map from `/etc/passwd` (`name:password:uid:...`); the first
+/// line for a UID wins, as with a linear lookup.
+#[cfg(unix)]
+fn read_passwd() -> HashMap<u32, String> {
+    let mut names = HashMap::new();
+    let passwd = std::fs::read_to_string("/etc/passwd").unwrap_or_default();
+    for line in passwd.lines() {
+        let mut fields = line.split(':');
+        let (Some(name), Some(uid)) = (fields.next(), fields.nth(1)) else {
+            continue;
+        };
+        if let Ok(uid) = uid.parse() {
+            names.entry(uid).or_insert_with(|| name.to_string());
+        }
+    }
+    names
+}
+
+/// ELF `e_machine` values.
+const ELF_MACHINES: &[(u32, &str)] = &[
+    (0x03, "x86"),
+    (0x3e, "x86_64"),
+    (0x28, "arm"),
+    (0xb7, "aarch64"),
+    (0xf3, "riscv"),
+    (0x08, "mips"),
+    (0x14, "powerpc"),
+    (0x15, "powerpc64"),
+    (0x16, "s390x"),
+    (0x102, "loongarch64"),
+];
+
+/// Mach-O `cputype` values; 64-bit variants set bit 24.
+const MACHO_CPUS: &[(u32, &str)] = &[
+    (0x07, "x86"),
+    (0x0100_0007, "x86_64"),
+    (0x0c, "arm"),
+    (0x0100_000c, "aarch64"),
+];
+
+/// PE/COFF `Machine` values.
+const PE_MACHINES: &[(u32, &str)] = &[
+    (0x014c, "x86"),
+    (0x8664, "x86_64"),
+    (0x01c4, "arm"),
+    (0xaa64, "aarch64"),
+];
+
+/// CPU architecture from an ELF, Mach-O or PE header, e.g. `x86_64`.
+pub fn binary_arch(path: &Path) -> String {
+    let mut header = Vec::with_capacity(1024);
+    let read = File::open(path).and_then(|f| f.take(1024).read_to_end(&mut header));
+    let arch = read.ok().and_then(|_| header_arch(&header));
+    arch.unwrap_or(UNKNOWN_ARCH).to_string()
+}
+
+fn header_arch(header: &[u8]) -> Option<&'static str> {
+    let u16_at = |at: usize, big_endian: bool| {
+        let bytes: [u8; 2] = header.get(at..at + 2)?.try_into().ok()?;
+        let value = if big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        };
+        Some(u32::from(value))
+    };
+    let u32_at = |at: usize| Some(u32::from_le_bytes(header.get(at..at + 4)?.try_into().ok()?));
+    match header.get(..4)? {
+        [0x7f, b'E', b'L', b'F'] => lookup(ELF_MACHINES, u16_at(18, header.get(5) == Some(&2))?),
+        [0xce | 0xcf, 0xfa, 0xed, 0xfe] => lookup(MACHO_CPUS, u32_at(4)?),
+        [0xca, 0xfe, 0xba, 0xbe] => Some("universal"),
+        [b'M', b'Z', ..] => {
+            let pe = u32_at(0x3c)? as usize;
+            (header.get(pe..pe + 4)? == b"PE\0\0").then_some(())?;
+            lookup(PE_MACHINES, u16_at(pe + 4, false)?)
+        }
+        _ => None,
+    }
+}
+
+fn lookup(table: &[(u32, &'static str)], code: u32) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map(|(_, name)| *name)
+}
@@ -4,9 +4,18 @@
 //! List installed binaries for sw-install.
 
 mod binaries;
+mod format;
+mod inspect;
 mod list;
+mod pattern;
+mod render;
 mod sort;
+mod watch;
 
+pub use binaries::{BinaryInfo, Listing};
+pub use format::{InvalidListFormat, ListFormat, ListOptions};
 pub use list::Lister;
-pub use sort::{InvalidSortOrder, SortOrder};
+pub use pattern::{InvalidNamePattern, NamePattern};
+pub use sort::{InstallDate, InvalidInstallDate, InvalidSortOrder, SortOrder};
 pub use sw_install_core::format_time_ago;
+pub use watch::CLEAR_SCREEN;
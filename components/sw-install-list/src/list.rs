@@ -1,16 +1,37 @@
 // Copyright (c) 2025 Michael A Wright
 // Licensed under the MIT License
 
-use crate::binaries::{collect_binaries, get_bin_dir};
-use crate::sort::SortOrder;
+use crate::binaries::{
+    BinaryInfo, EntryFilter, Listing, collect_binaries, dedupe_by_checksum, get_bin_dir,
+};
+use crate::format::{ListFormat, ListOptions};
+use crate::pattern::NamePattern;
+use crate::render::{write_csv, write_json, write_text};
+use crate::sort::{InstallDate, SortOrder};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
 use std::path::PathBuf;
-use std::time::SystemTime;
-use sw_install_core::format_time_ago;
-use sw_install_core::{NormalOutput, Result};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sw_install_core::{
+    IoResultExt, NormalOutput, Result, expand_path, pretty_json, resolve_install_dir,
+};
+use sw_install_manifest::{FileManifestStore, ManifestStore};
 
 pub struct Lister<'a> {
     test_dir: Option<PathBuf>,
+    install_dir: Option<PathBuf>,
+    /// Arbitrary directory to list instead of the install dir.
+    dir: Option<PathBuf>,
     sort_order: SortOrder,
+    options: ListOptions,
+    grep: Option<NamePattern>,
+    stale_after: Option<Duration>,
+    installed_after: Option<SystemTime>,
+    installed_before: Option<SystemTime>,
+    /// Writes the listing here instead of stdout.
+    output_file: Option<PathBuf>,
+    /// Overrides the install dir's manifest file, e.g. in tests.
+    store: Option<&'a dyn ManifestStore>,
     output: &'a NormalOutput,
 }
 
@@ -18,36 +39,200 @@ impl<'a> Lister<'a> {
     pub fn new(test_dir: Option<PathBuf>, sort_order: SortOrder, output: &'a NormalOutput) -> Self {
         Self {
             test_dir,
+            install_dir: None,
+            dir: None,
             sort_order,
+            options: ListOptions::default(),
+            grep: None,
+            stale_after: None,
+            installed_after: None,
+            installed_before: None,
+            output_file: None,
+            store: None,
             output,
         }
     }
 
+    pub fn with_install_dir(mut self, install_dir: Option<PathBuf>) -> Self {
+        self.install_dir = install_dir;
+        self
+    }
+
+    /// Lists `dir` (e.g. `~/.cargo/bin`) instead of the install dir,
+    /// overriding both `install_dir` and `test_dir`.
+    pub fn with_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.dir = dir;
+        self
+    }
+
+    pub fn with_format(mut self, format: ListFormat) -> Self {
+        self.options.format = format;
+        self
+    }
+
+    /// Keeps only names matching `grep`.
+    pub fn with_grep(mut self, grep: Option<NamePattern>) -> Self {
+        self.grep = grep;
+        self
+    }
+
+    /// Keeps only binaries last modified more than `days` days ago.
+    pub fn with_stale_days(mut self, days: Option<u64>) -> Self {
+        self.stale_after = days.map(|d| Duration::from_secs(d * 24 * 60 * 60));
+        self
+    }
+
+    /// Keeps only binaries installed at or after `after` and strictly before
+    /// `before`, by manifest install time or else mtime.
+    pub fn with_installed_range(
+        mut self,
+        after: Option<InstallDate>,
+        before: Option<InstallDate>,
+    ) -> Self {
+        self.installed_after = after.map(|d| d.0);
+        self.installed_before = before.map(|d| d.0);
+        self
+    }
+
+    /// Writes the listing to `path`, creating its parent dirs, instead of
+    /// stdout.
+    pub fn with_output_file(mut self, path: Option<PathBuf>) -> Self {
+        self.output_file = path;
+        self
+    }
+
+    /// Reads install records from `store` instead of the manifest file.
+    pub fn with_manifest_store(mut self, store: &'a dyn ManifestStore) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    pub fn with_options(mut self, options: ListOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Prints the listing and returns the names in display order.
     pub fn list(&self) -> Result<Vec<String>> {
+        let bins = self.collect_and_print()?;
+        // Move each name out rather than cloning it; the listing is done
+        Ok(bins.into_iter().map(|b| b.name).collect())
+    }
+
+    /// Prints the listing and returns how many binaries it showed.
+    pub fn print(&self) -> Result<usize> {
+        Ok(self.collect_and_print()?.len())
+    }
+
+    fn collect_and_print(&self) -> Result<Vec<BinaryInfo>> {
         self.output.info("Listing installed binaries...");
-        let bin_dir = get_bin_dir(&self.test_dir)?;
-        let mut bins = collect_binaries(&bin_dir)?;
-        sort_binaries(&mut bins, self.sort_order);
-        print_binaries(&bins);
-        Ok(bins.into_iter().map(|(n, _)| n).collect())
+        let listing = self.scan()?;
+        self.warn_about(&listing);
+        let Some(ref path) = self.output_file else {
+            let mut out = BufWriter::new(io::stdout().lock());
+            self.write(&mut out, &listing)?;
+            out.flush()?;
+            return Ok(listing.binaries);
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).io_context("creating directory", parent)?;
+        }
+        let file = File::create(path).io_context("creating", path)?;
+        let mut out = BufWriter::new(file);
+        self.write(&mut out, &listing)?;
+        out.flush().io_context("writing", path)?;
+        Ok(listing.binaries)
     }
-}
 
-fn sort_binaries(bins: &mut [(String, SystemTime)], order: SortOrder) {
-    match order {
-        SortOrder::Name => bins.sort_by(|a, b| a.0.cmp(&b.0)),
-        SortOrder::Oldest => bins.sort_by(|a, b| a.1.cmp(&b.1)),
-        SortOrder::Newest => bins.sort_by(|a, b| b.1.cmp(&a.1)),
+    /// Installed binaries in the configured sort order.
+    pub fn collect(&self) -> Result<Vec<BinaryInfo>> {
+        Ok(self.scan()?.binaries)
     }
-}
 
-fn print_binaries(bins: &[(String, SystemTime)]) {
-    if bins.is_empty() {
-        println!("No binaries installed");
-        return;
+    /// Installed binaries in the configured sort order, plus the entries
+    /// that could not be read.
+    pub fn scan(&self) -> Result<Listing> {
+        let bin_dir = get_bin_dir(self.listed_dir()?, self.dir.is_some())?;
+        let file_store = FileManifestStore::new(&bin_dir, self.output);
+        let store = self.store.unwrap_or(&file_store);
+        let filter = EntryFilter {
+            grep: self.grep.as_ref(),
+            // An age too large to subtract keeps nothing, as no mtime predates it
+            modified_before: self
+                .stale_after
+                .map(|age| SystemTime::now().checked_sub(age).unwrap_or(UNIX_EPOCH)),
+            installed_after: self.installed_after,
+            installed_before: self.installed_before,
+        };
+        let mut listing = collect_binaries(&bin_dir, &self.options, &filter, store, self.output)?;
+        sort_binaries(&mut listing.binaries, self.sort_order);
+        if self.options.dedupe {
+            listing.binaries = dedupe_by_checksum(listing.binaries);
+        }
+        Ok(listing)
     }
-    let now = SystemTime::now();
-    for (name, time) in bins {
-        println!("{} ({})", name, format_time_ago(now, *time));
+
+    /// Warns once per listing about entries that need attention, so
+    /// `collect` stays free of side effects and watch frames stay quiet.
+    pub(crate) fn warn_about(&self, listing: &Listing) {
+        // Only the JSON object has room for errors; elsewhere they go to stderr
+        if self.options.format != ListFormat::Json || self.options.json_array {
+            for (path, e) in &listing.unreadable {
+                self.output
+                    .warn(&format!("Could not read {}: {e}", path.display()));
+            }
+        }
+        for bin in listing.binaries.iter().filter(|b| b.foreign_owner) {
+            let owner = bin.owner.as_deref().unwrap_or("another user");
+            self.output.warn(&format!(
+                "{} is owned by {owner}, not the current user",
+                bin.name
+            ));
+        }
+    }
+
+    /// `--dir` when given, otherwise the resolved install dir.
+    fn listed_dir(&self) -> Result<PathBuf> {
+        match self.dir {
+            Some(ref dir) => Ok(expand_path(dir)),
+            None => resolve_install_dir(self.install_dir.as_deref(), self.test_dir.as_deref()),
+        }
+    }
+
+    /// Formats `listing` as the list command would print it.
+    pub fn render(&self, listing: &Listing) -> String {
+        let mut buf = Vec::new();
+        // Writing to a Vec cannot fail
+        let _ = self.write(&mut buf, listing);
+        String::from_utf8_lossy(&buf).into_owned()
+    }
+
+    /// Streams the formatted `listing` to `out`.
+    pub fn write(&self, out: &mut impl Write, listing: &Listing) -> io::Result<()> {
+        let now = SystemTime::now();
+        let install_dir = self.listed_dir().ok();
+        let install_dir = install_dir.as_deref();
+        let (bins, errors) = (&listing.binaries, &listing.unreadable);
+        match self.options.format {
+            ListFormat::Text => write_text(out, bins, now, &self.options),
+            ListFormat::Json if self.options.pretty => {
+                let mut compact = Vec::new();
+                write_json(&mut compact, bins, now, &self.options, install_dir, errors)?;
+                writeln!(out, "{}", pretty_json(&String::from_utf8_lossy(&compact)))
+            }
+            ListFormat::Json => write_json(out, bins, now, &self.options, install_dir, errors),
+            ListFormat::Csv => write_csv(out, bins),
+        }
+    }
+}
+
+fn sort_binaries(bins: &mut [BinaryInfo], order: SortOrder) {
+    match order {
+        SortOrder::Name => bins.sort_by(|a, b| a.name.cmp(&b.name)),
+        // Binaries installed together share an mtime; names break the tie
+        SortOrder::Oldest => bins.sort_by(|a, b| (a.modified, &a.name).cmp(&(b.modified, &b.name))),
+        SortOrder::Newest => {
+            bins.sort_by(|a, b| b.modified.cmp(&a.modified).then(a.name.cmp(&b.name)))
+        }
     }
 }
@@ -0,0 +1,262 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+//! The `--grep` name pattern: a documented subset of regular-expression
+//! syntax, not a full regex engine. To be replaced by the `regex` crate
+//! once the build can vendor it.
+//!
+//! Supports literals, `.`, `^`/`$` anchors, `*`/`+`/`?`, bracket classes
+//! (`[a-z]`, `[^0-9]`), `\d \w \s` (and negations), escaped metacharacters,
+//! and top-level alternation with `|`. Groups and counted repetition are
+//! rejected with a clear error rather than silently mismatching. Matching
+//! remembers failed (node, position) pairs, so it stays polynomial for any
+//! pattern instead of backtracking exponentially.
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+/// The syntax `--grep` accepts, for error hints.
+const SUPPORTED_SYNTAX: &str = ". * + ? [...] \\d \\w \\s ^ $ and |";
+
+/// A compiled `--grep` pattern, matched anywhere in a name unless anchored.
+#[derive(Debug, Clone)]
+pub struct NamePattern {
+    alternatives: Vec<Sequence>,
+}
+
+#[derive(Debug, Clone)]
+pub struct InvalidNamePattern {
+    pub pattern: String,
+    pub reason: String,
+}
+
+impl std::fmt::Display for InvalidNamePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid pattern '{}': {}\nHint: --grep supports only {SUPPORTED_SYNTAX}",
+            self.pattern, self.reason
+        )
+    }
+}
+
+impl std::error::Error for InvalidNamePattern {}
+
+#[derive(Debug, Clone, Default)]
+struct Sequence {
+    start: bool,
+    end: bool,
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    atom: Atom,
+    min: usize,
+    max: usize,
+}
+
+#[derive(Debug, Clone)]
+enum Atom {
+    Any,
+    Char(char),
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+impl NamePattern {
+    pub fn is_match(&self, name: &str) -> bool {
+        let text: Vec<char> = name.chars().collect();
+        self.alternatives.iter().any(|seq| seq.is_match(&text))
+    }
+}
+
+impl FromStr for NamePattern {
+    type Err = InvalidNamePattern;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let alternatives = split_alternatives(s)
+            .iter()
+            .map(|alt| parse_sequence(alt))
+            .collect::<Result<_, String>>()
+            .map_err(|reason| InvalidNamePattern {
+                pattern: s.to_string(),
+                reason,
+            })?;
+        Ok(Self { alternatives })
+    }
+}
+
+impl Sequence {
+    fn is_match(&self, text: &[char]) -> bool {
+        let last_start = if self.start { 0 } else { text.len() };
+        let mut failed = HashSet::new();
+        (0..=last_start).any(|pos| self.match_here(0, text, pos, &mut failed))
+    }
+
+    /// Whether `nodes[index..]` matches `text` from `pos`. A (node, pos)
+    /// pair that failed once fails again, so it is never retried.
+    fn match_here(
+        &self,
+        index: usize,
+        text: &[char],
+        pos: usize,
+        failed: &mut HashSet<(usize, usize)>,
+    ) -> bool {
+        let Some(node) = self.nodes.get(index) else {
+            return !self.end || pos == text.len();
+        };
+        if failed.contains(&(index, pos)) {
+            return false;
+        }
+        let available = text[pos..]
+            .iter()
+            .take(node.max)
+            .take_while(|c| node.atom.matches(**c))
+            .count();
+        let matched = (node.min..=available)
+            .rev()
+            .any(|n| self.match_here(index + 1, text, pos + n, failed));
+        if !matched {
+            failed.insert((index, pos));
+        }
+        matched
+    }
+}
+
+impl Atom {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Atom::Any => true,
+            Atom::Char(expected) => c == *expected,
+            Atom::Class { negated, ranges } => {
+                ranges.iter().any(|(lo, hi)| (*lo..=*hi).contains(&c)) != *negated
+            }
+        }
+    }
+}
+
+/// Splits on `|` outside bracket classes and escapes.
+fn split_alternatives(pattern: &str) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let (mut escaped, mut in_class) = (false, false);
+    for c in pattern.chars() {
+        match c {
+            '|' if !escaped && !in_class => parts.push(String::new()),
+            _ => parts.last_mut().unwrap().push(c),
+        }
+        in_class = match c {
+            '[' if !escaped => true,
+            ']' if !escaped => false,
+            _ => in_class,
+        };
+        escaped = c == '\\' && !escaped;
+    }
+    parts
+}
+
+fn parse_sequence(alternative: &str) -> Result<Sequence, String> {
+    let mut chars: Vec<char> = alternative.chars().collect();
+    let mut seq = Sequence::default();
+    if chars.first() == Some(&'^') {
+        seq.start = true;
+        chars.remove(0);
+    }
+    if chars.last() == Some(&'$') && !ends_with_escape(&chars[..chars.len() - 1]) {
+        seq.end = true;
+        chars.pop();
+    }
+    let mut i = 0;
+    while i < chars.len() {
+        let (atom, next) = parse_atom(&chars, i)?;
+        let (min, max, next) = parse_quantifier(&chars, next);
+        seq.nodes.push(Node { atom, min, max });
+        i = next;
+    }
+    Ok(seq)
+}
+
+fn ends_with_escape(chars: &[char]) -> bool {
+    chars.iter().rev().take_while(|c| **c == '\\').count() % 2 == 1
+}
+
+fn parse_quantifier(chars: &[char], i: usize) -> (usize, usize, usize) {
+    match chars.get(i) {
+        Some('*') => (0, usize::MAX, i + 1),
+        Some('+') => (1, usize::MAX, i + 1),
+        Some('?') => (0, 1, i + 1),
+        _ => (1, 1, i),
+    }
+}
+
+fn parse_atom(chars: &[char], i: usize) -> Result<(Atom, usize), String> {
+    match chars[i] {
+        '.' => Ok((Atom::Any, i + 1)),
+        '[' => parse_class(chars, i + 1),
+        '\\' => {
+            let c = chars.get(i + 1).ok_or("trailing backslash")?;
+            Ok((parse_escape(*c)?, i + 2))
+        }
+        '*' | '+' | '?' => Err(format!("'{}' has nothing to repeat", chars[i])),
+        '(' | ')' | '{' | '}' => Err(format!("'{}' (groups/counts) is not supported", chars[i])),
+        '^' | '$' => Err(format!("anchor '{}' is only allowed at the ends", chars[i])),
+        c => Ok((Atom::Char(c), i + 1)),
+    }
+}
+
+fn parse_escape(c: char) -> Result<Atom, String> {
+    let class = |negated: bool, ranges: &[(char, char)]| Atom::Class {
+        negated,
+        ranges: ranges.to_vec(),
+    };
+    const DIGIT: &[(char, char)] = &[('0', '9')];
+    const WORD: &[(char, char)] = &[('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')];
+    const SPACE: &[(char, char)] = &[(' ', ' '), ('\t', '\r')];
+    match c {
+        'd' | 'D' => Ok(class(c == 'D', DIGIT)),
+        'w' | 'W' => Ok(class(c == 'W', WORD)),
+        's' | 'S' => Ok(class(c == 'S', SPACE)),
+        c if c.is_ascii_alphanumeric() => Err(format!("unknown escape '\\{c}'")),
+        c => Ok(Atom::Char(c)),
+    }
+}
+
+fn parse_class(chars: &[char], mut i: usize) -> Result<(Atom, usize), String> {
+    let negated = chars.get(i) == Some(&'^');
+    if negated {
+        i += 1;
+    }
+    let mut ranges = Vec::new();
+    while let Some(&c) = chars.get(i) {
+        if c == ']' && !ranges.is_empty() {
+            return Ok((Atom::Class { negated, ranges }, i + 1));
+        }
+        let (lo, next) = class_char(chars, i)?;
+        if chars.get(next) == Some(&'-') && chars.get(next + 1).is_some_and(|c| *c != ']') {
+            let (hi, after) = class_char(chars, next + 1)?;
+            if hi < lo {
+                return Err(format!("invalid range '{lo}-{hi}'"));
+            }
+            ranges.push((lo, hi));
+            i = after;
+        } else {
+            ranges.push((lo, lo));
+            i = next;
+        }
+    }
+    Err("unterminated character class".to_string())
+}
+
+fn class_char(chars: &[char], i: usize) -> Result<(char, usize), String> {
+    match chars[i] {
+        '\\' => match chars.get(i + 1) {
+            Some(c) if c.is_ascii_alphanumeric() => {
+                Err(format!("escape '\\{c}' is not supported inside [...]"))
+            }
+            Some(c) => Ok((*c, i + 2)),
+            None => Err("trailing backslash".to_string()),
+        },
+        c => Ok((c, i + 1)),
+    }
+}
@@ -0,0 +1,263 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+//! Writers for the list output. Entries are written one at a time so
+//! large install dirs never need a fully rendered copy in memory.
+
+use crate::binaries::{BinaryInfo, UNKNOWN_SOURCE, group_by_source};
+use crate::format::ListOptions;
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use sw_install_core::{JSON_SCHEMA_VERSION, format_size, format_time_ago, json_string};
+
+pub fn write_text(
+    out: &mut impl Write,
+    bins: &[BinaryInfo],
+    now: SystemTime,
+    options: &ListOptions,
+) -> io::Result<()> {
+    if options.names_only {
+        return bins.iter().try_for_each(|b| writeln!(out, "{}", b.name));
+    }
+    if bins.is_empty() {
+        if options.quiet {
+            return Ok(());
+        }
+        return writeln!(out, "No binaries installed");
+    }
+    if options.group_by_source {
+        write_grouped(out, bins, now, options.long)?;
+    } else {
+        write_entries(out, bins, now, options.long)?;
+    }
+    if options.total {
+        write_total_footer(out, bins)?;
+    }
+    Ok(())
+}
+
+fn write_entries(
+    out: &mut impl Write,
+    bins: &[BinaryInfo],
+    now: SystemTime,
+    long: bool,
+) -> io::Result<()> {
+    if long {
+        return write_long(out, bins, now);
+    }
+    for b in bins {
+        let also = if b.aliases.is_empty() {
+            String::new()
+        } else {
+            format!(" [also: {}]", b.aliases.join(", "))
+        };
+        writeln!(
+            out,
+            "{} ({}){}",
+            b.name,
+            format_time_ago(now, b.modified),
+            also
+        )?;
+    }
+    Ok(())
+}
+
+/// One `<project>:` heading per source, blank-line separated.
+fn write_grouped(
+    out: &mut impl Write,
+    bins: &[BinaryInfo],
+    now: SystemTime,
+    long: bool,
+) -> io::Result<()> {
+    for (i, (source, group)) in group_by_source(bins).into_iter().enumerate() {
+        if i > 0 {
+            writeln!(out)?;
+        }
+        let heading = source.map_or(UNKNOWN_SOURCE.to_string(), |s| s.display().to_string());
+        writeln!(out, "{heading}:")?;
+        write_entries(out, &group, now, long)?;
+    }
+    Ok(())
+}
+
+pub fn write_json(
+    out: &mut impl Write,
+    bins: &[BinaryInfo],
+    now: SystemTime,
+    options: &ListOptions,
+    install_dir: Option<&Path>,
+    errors: &[(PathBuf, io::Error)],
+) -> io::Result<()> {
+    if options.json_array {
+        write_json_entries(out, bins, now, options)?;
+        return writeln!(out);
+    }
+    write!(out, "{{\"schema_version\":{},", JSON_SCHEMA_VERSION)?;
+    if let Some(dir) = install_dir {
+        let dir = json_string(&dir.display().to_string());
+        write!(out, "\"install_dir\":{dir},")?;
+    }
+    write!(out, "\"count\":{},", bins.len())?;
+    if options.total {
+        write!(out, "\"total_bytes\":{},", total_bytes(bins))?;
+    }
+    write!(out, "\"entries\":")?;
+    write_json_entries(out, bins, now, options)?;
+    write_json_errors(out, errors)?;
+    writeln!(out, "}}")
+}
+
+/// Install dir entries left out of `entries` because they could not be read.
+fn write_json_errors(out: &mut impl Write, errors: &[(PathBuf, io::Error)]) -> io::Result<()> {
+    let errors: Vec<String> = errors
+        .iter()
+        .map(|(path, e)| {
+            format!(
+                "{{\"path\":{},\"error\":{}}}",
+                json_string(&path.display().to_string()),
+                json_string(&e.to_string())
+            )
+        })
+        .collect();
+    write!(out, ",\"errors\":[{}]", errors.join(","))
+}
+
+fn write_json_entries(
+    out: &mut impl Write,
+    bins: &[BinaryInfo],
+    now: SystemTime,
+    options: &ListOptions,
+) -> io::Result<()> {
+    write!(out, "[")?;
+    for (i, b) in bins.iter().enumerate() {
+        let separator = if i == 0 { "" } else { "," };
+        write!(
+            out,
+            "{separator}{{\"name\":{},\"size_bytes\":{},\"modified_unix_secs\":{},\"modified_ago\":{},\"debug\":{},\"build_type\":{},\"target_triple\":{},\"on_path\":{},\"owner\":{},\"foreign_owner\":{},\"arch\":{}",
+            json_string(&b.name),
+            b.size,
+            unix_secs(b.modified),
+            json_string(&format_time_ago(now, b.modified)),
+            b.debug,
+            b.build_type.map_or("null".to_string(), json_string),
+            b.target_triple
+                .as_deref()
+                .map_or("null".to_string(), json_string),
+            b.on_path,
+            b.owner.as_deref().map_or("null".to_string(), json_string),
+            b.foreign_owner,
+            b.arch.as_deref().map_or("null".to_string(), json_string)
+        )?;
+        write_link_fields(out, b)?;
+        if options.dedupe {
+            let aliases: Vec<String> = b.aliases.iter().map(|a| json_string(a)).collect();
+            write!(out, ",\"aliases\":[{}]", aliases.join(","))?;
+        }
+        write!(out, "}}")?;
+    }
+    write!(out, "]")
+}
+
+/// Where the entry is installed, and whether as a plain file or a
+/// (possibly dangling) link.
+fn write_link_fields(out: &mut impl Write, b: &BinaryInfo) -> io::Result<()> {
+    let target = b.link_target.as_deref().map_or("null".to_string(), |t| {
+        json_string(&t.display().to_string())
+    });
+    write!(
+        out,
+        ",\"path\":{},\"is_symlink\":{},\"link_target\":{},\"broken_symlink\":{}",
+        json_string(&absolute_path(&b.path).display().to_string()),
+        b.is_symlink,
+        target,
+        b.is_symlink && b.link_target.is_none()
+    )
+}
+
+/// One RFC 4180 row per binary under a fixed header.
+pub fn write_csv(out: &mut impl Write, bins: &[BinaryInfo]) -> io::Result<()> {
+    writeln!(out, "name,size_bytes,modified_iso,source")?;
+    for b in bins {
+        let modified = DateTime::<Utc>::from(b.modified).to_rfc3339_opts(SecondsFormat::Secs, true);
+        let source = b
+            .source
+            .as_ref()
+            .map(|s| s.display().to_string())
+            .unwrap_or_default();
+        writeln!(
+            out,
+            "{},{},{},{}",
+            csv_field(&b.name),
+            b.size,
+            modified,
+            csv_field(&source)
+        )?;
+    }
+    Ok(())
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Aligned `name  size  arch  owner  age` columns, marking debug builds and
+/// binaries another user owns.
+fn write_long(out: &mut impl Write, bins: &[BinaryInfo], now: SystemTime) -> io::Result<()> {
+    let width = bins.iter().map(|b| b.name.len()).max().unwrap_or(0);
+    let arch = |b: &BinaryInfo| b.arch.clone().unwrap_or_else(|| "-".to_string());
+    let arch_width = bins.iter().map(|b| arch(b).len()).max().unwrap_or(0);
+    let owner = |b: &BinaryInfo| b.owner.clone().unwrap_or_else(|| "-".to_string());
+    let owner_width = bins.iter().map(|b| owner(b).len()).max().unwrap_or(0);
+    for b in bins {
+        let marker = if b.debug { " (debug)" } else { "" };
+        let foreign = if b.foreign_owner {
+            " (other owner)"
+        } else {
+            ""
+        };
+        writeln!(
+            out,
+            "{:<width$}  {:>10}  {:<arch_width$}  {:<owner_width$}  {}{}{}",
+            b.name,
+            format_size(b.size),
+            arch(b),
+            owner(b),
+            format_time_ago(now, b.modified),
+            marker,
+            foreign
+        )?;
+    }
+    Ok(())
+}
+
+fn write_total_footer(out: &mut impl Write, bins: &[BinaryInfo]) -> io::Result<()> {
+    let noun = if bins.len() == 1 {
+        "binary"
+    } else {
+        "binaries"
+    };
+    let size = format_size(total_bytes(bins));
+    writeln!(out, "{} {}, {} total", bins.len(), noun, size)
+}
+
+/// `path` made absolute without following symlinks, so a linked entry
+/// keeps its own path; `link_target` reports where it points.
+fn absolute_path(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Whole seconds since the Unix epoch; pre-epoch times clamp to 0.
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn total_bytes(bins: &[BinaryInfo]) -> u64 {
+    bins.iter().map(|b| b.size).sum()
+}
@@ -1,7 +1,9 @@
 // Copyright (c) 2025 Michael A Wright
 // Licensed under the MIT License
 
+use chrono::{DateTime, NaiveDate, Utc};
 use std::str::FromStr;
+use std::time::SystemTime;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SortOrder {
@@ -36,3 +38,37 @@ impl FromStr for SortOrder {
         }
     }
 }
+
+/// An ISO-8601 date (`2025-06-01`, midnight UTC) or RFC 3339 timestamp
+/// (`2025-06-01T12:00:00Z`) bounding `--installed-before/--installed-after`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InstallDate(pub SystemTime);
+
+#[derive(Debug, Clone)]
+pub struct InvalidInstallDate(pub String);
+
+impl std::fmt::Display for InvalidInstallDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid date '{}'. Use YYYY-MM-DD or an RFC 3339 timestamp such as 2025-06-01T12:00:00Z",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for InvalidInstallDate {}
+
+impl FromStr for InstallDate {
+    type Err = InvalidInstallDate;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let instant = match DateTime::parse_from_rfc3339(s) {
+            Ok(timestamp) => timestamp.with_timezone(&Utc),
+            Err(_) => NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .map_err(|_| InvalidInstallDate(s.to_string()))?
+                .and_time(chrono::NaiveTime::MIN)
+                .and_utc(),
+        };
+        Ok(InstallDate(instant.into()))
+    }
+}
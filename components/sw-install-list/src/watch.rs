@@ -0,0 +1,149 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use crate::binaries::Listing;
+use crate::list::Lister;
+use std::io::{self, BufWriter, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+use sw_install_core::Result;
+
+/// ANSI erase-display plus cursor-home, so each refresh redraws in place.
+pub const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// How often the sleep between refreshes checks for Ctrl-C.
+const POLL_STEP: Duration = Duration::from_millis(100);
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+impl Lister<'_> {
+    /// Draws one watch frame to `out`: clear, header, listing. Returns how
+    /// many binaries it showed.
+    pub fn refresh(&self, out: &mut impl Write, interval: Duration) -> Result<usize> {
+        Ok(self.draw(out, interval)?.binaries.len())
+    }
+
+    fn draw(&self, out: &mut impl Write, interval: Duration) -> Result<Listing> {
+        let listing = self.scan()?;
+        write!(out, "{CLEAR_SCREEN}")?;
+        writeln!(
+            out,
+            "Every {}s: sw-install --list (Ctrl-C to stop)\n",
+            interval.as_secs()
+        )?;
+        self.write(out, &listing)?;
+        out.flush()?;
+        Ok(listing)
+    }
+
+    /// Redraws the listing on stdout every `interval` until Ctrl-C.
+    pub fn watch(&self, interval: Duration) -> Result<()> {
+        INTERRUPTED.store(false, Ordering::SeqCst);
+        #[cfg(unix)]
+        let previous = signals::install();
+        let result = self.watch_until_interrupted(interval);
+        #[cfg(unix)]
+        signals::restore(&previous);
+        // Leave the prompt below the last frame rather than on top of it
+        println!();
+        result
+    }
+
+    fn watch_until_interrupted(&self, interval: Duration) -> Result<()> {
+        let mut first_frame = true;
+        while !INTERRUPTED.load(Ordering::SeqCst) {
+            let mut out = BufWriter::new(io::stdout().lock());
+            let listing = self.draw(&mut out, interval)?;
+            drop(out);
+            // Repeating them every frame would flood stderr and inflate the
+            // --fail-on-warning count
+            if first_frame {
+                self.warn_about(&listing);
+                first_frame = false;
+            }
+            let mut waited = Duration::ZERO;
+            while waited < interval && !INTERRUPTED.load(Ordering::SeqCst) {
+                thread::sleep(POLL_STEP);
+                waited += POLL_STEP;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+mod signals {
+    use std::ptr::null_mut;
+
+    extern "C" fn on_interrupt(_signal: libc::c_int) {
+        // An atomic store is async-signal-safe
+        super::INTERRUPTED.store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    /// Routes SIGINT to the interrupt flag, returning the disposition it
+    /// replaced.
+    pub fn install() -> libc::sigaction {
+        // SAFETY: all-zero is a valid sigaction (SIG_DFL, empty mask, no flags).
+        let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+        action.sa_sigaction = on_interrupt as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        // SAFETY: as above.
+        let mut previous: libc::sigaction = unsafe { std::mem::zeroed() };
+        // SAFETY: on_interrupt only performs an atomic store.
+        unsafe { libc::sigaction(libc::SIGINT, &action, &mut previous) };
+        previous
+    }
+
+    /// Reinstates the disposition `install` replaced.
+    pub fn restore(previous: &libc::sigaction) {
+        // SAFETY: `previous` was filled in by sigaction itself.
+        unsafe { libc::sigaction(libc::SIGINT, previous, null_mut()) };
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::sort::SortOrder;
+    use std::sync::Arc;
+    use sw_install_core::NormalOutput;
+    use tempfile::TempDir;
+
+    #[test]
+    fn watch_restores_the_previous_interrupt_handler() {
+        extern "C" fn embedder_handler(_: libc::c_int) {}
+        let handler = embedder_handler as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        let current = || {
+            // SAFETY: querying a disposition with a null new action is sound
+            let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+            unsafe { libc::sigaction(libc::SIGINT, std::ptr::null(), &mut action) };
+            action.sa_sigaction
+        };
+        // SAFETY: embedder_handler does nothing
+        let before = unsafe { libc::signal(libc::SIGINT, handler) };
+
+        let dir = TempDir::new().unwrap();
+        let output = NormalOutput::quiet();
+        let lister = Lister::new(Some(dir.path().to_path_buf()), SortOrder::Name, &output);
+        // Keep interrupting until watch returns; signals that land before
+        // watch installs its handler reach the no-op embedder handler
+        let done = Arc::new(AtomicBool::new(false));
+        let interrupter = thread::spawn({
+            let done = Arc::clone(&done);
+            move || {
+                while !done.load(Ordering::SeqCst) {
+                    thread::sleep(POLL_STEP);
+                    // SAFETY: SIGINT is handled by one of the handlers above
+                    unsafe { libc::kill(libc::getpid(), libc::SIGINT) };
+                }
+            }
+        });
+        lister.watch(Duration::from_secs(1)).unwrap();
+        done.store(true, Ordering::SeqCst);
+        interrupter.join().unwrap();
+
+        assert_eq!(current(), handler);
+        // SAFETY: reinstating the disposition found at the start
+        unsafe { libc::signal(libc::SIGINT, before) };
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use std::process::{Command, ExitStatus};
+use std::thread;
+use std::time::{Duration, Instant};
+use sw_install_core::{InstallError, Result};
+
+/// How often a running command is polled for completion.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Runs `cmd` to completion, killing it once `timeout` elapses.
+pub fn run_with_timeout(cmd: &mut Command, timeout: Duration) -> Result<ExitStatus> {
+    let mut child = cmd.spawn()?;
+    let started = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        if started.elapsed() >= timeout {
+            // The child may exit between try_wait and kill; either way reap it
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(InstallError::CommandTimedOut(
+                describe(cmd),
+                timeout.as_secs(),
+            ));
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn describe(cmd: &Command) -> String {
+    let mut parts = vec![cmd.get_program().to_string_lossy().into_owned()];
+    parts.extend(cmd.get_args().map(|a| a.to_string_lossy().into_owned()));
+    parts.join(" ")
+}
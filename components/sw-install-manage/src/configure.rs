@@ -0,0 +1,52 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+//! The shell-config half of [`Setup`]: choosing the rc file and writing
+//! the PATH line into it.
+
+use crate::setup::Setup;
+use crate::shell::{find_shell_config, refresh_path_config, write_path_config};
+use std::path::{Path, PathBuf};
+use sw_install_core::{InstallError, Result, is_dir_on_path, path_dirs, resolve_install_dir};
+
+impl Setup<'_> {
+    /// Rewrites the shell config's sw-install block for the current install
+    /// dir, e.g. after switching to `--install-dir`, without duplicating it.
+    pub fn refresh_path(&self) -> Result<PathBuf> {
+        let install_dir =
+            resolve_install_dir(self.install_dir.as_deref(), self.test_dir.as_deref())?;
+        let shell_config = self.shell_config()?;
+        refresh_path_config(&shell_config, &install_dir, self.dry_run, self.output)
+    }
+
+    pub(crate) fn configure_shell(&self, install_dir: &Path) -> Result<PathBuf> {
+        let shell_config = self.shell_config()?;
+        self.output
+            .info("[3/3] Adding PATH configuration to shell...");
+        if self.test_dir.is_some() {
+            self.output.info("Test mode: skipping shell configuration");
+            return Ok(shell_config);
+        }
+        if !self.path_setup {
+            self.output
+                .info("--no-path-setup: skipping shell configuration");
+            return Ok(shell_config);
+        }
+        // A profile.d drop-in or another rc file may already provide it
+        if is_dir_on_path(install_dir, &path_dirs()) {
+            self.output
+                .info("Install dir already on PATH: skipping shell configuration");
+            return Ok(shell_config);
+        }
+        write_path_config(&shell_config, install_dir, self.dry_run, self.output)
+    }
+
+    /// The rc file for `--shell`, or for the detected shell.
+    fn shell_config(&self) -> Result<PathBuf> {
+        let home = std::env::var("HOME").map_err(|_| InstallError::HomeNotFound)?;
+        let shell_config = find_shell_config(Path::new(&home), self.shell.as_deref());
+        self.output
+            .info(&format!("Shell config: {}", shell_config.display()));
+        Ok(shell_config)
+    }
+}
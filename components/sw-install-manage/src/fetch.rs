@@ -0,0 +1,71 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use crate::command::run_with_timeout;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+use sw_install_core::{InstallError, NormalOutput, Result};
+use tempfile::TempDir;
+
+/// Start of every checkout directory's name, so callers can tell a
+/// throwaway clone from a project the user pointed at.
+pub const GIT_CHECKOUT_PREFIX: &str = "sw-install-git-";
+
+/// A shallow git clone in a temp dir, removed when dropped.
+pub struct GitCheckout {
+    dir: TempDir,
+}
+
+impl GitCheckout {
+    /// Shallow-clones `url` (at `reference`, a branch or tag, if given) with
+    /// depth 1, giving up after `timeout`.
+    pub fn fetch(
+        url: &str,
+        reference: Option<&str>,
+        timeout: Duration,
+        output: &NormalOutput,
+    ) -> Result<Self> {
+        require_tool("git")?;
+        require_tool("cargo")?;
+        let dir = Self::empty()?;
+        output.info(&format!("Cloning {url} into {}...", dir.path().display()));
+        let mut cmd = Command::new("git");
+        cmd.args(["clone", "--quiet", "--depth", "1"]);
+        if let Some(reference) = reference {
+            cmd.args(["--branch", reference]);
+        }
+        let status = run_with_timeout(cmd.arg(url).arg(dir.path()), timeout)?;
+        if !status.success() {
+            return Err(InstallError::GitCloneFailed(url.to_string()));
+        }
+        Ok(dir)
+    }
+
+    /// A fresh, empty checkout directory.
+    pub fn empty() -> Result<Self> {
+        let dir = tempfile::Builder::new()
+            .prefix(GIT_CHECKOUT_PREFIX)
+            .tempdir()?;
+        Ok(Self { dir })
+    }
+
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+}
+
+/// Fails with a clear error when `tool` cannot be run from PATH.
+pub fn require_tool(tool: &str) -> Result<()> {
+    let found = Command::new(tool)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success());
+    if found {
+        Ok(())
+    } else {
+        Err(InstallError::ToolNotFound(tool.to_string()))
+    }
+}
@@ -1,9 +1,18 @@
 // Copyright (c) 2025 Michael A Wright
 // Licensed under the MIT License
 
-//! Setup operations for sw-install.
+//! Setup and maintenance operations for sw-install.
 
+mod command;
+mod configure;
+mod fetch;
+mod repair;
 mod setup;
 mod shell;
+mod update;
 
+pub use command::run_with_timeout;
+pub use fetch::{GIT_CHECKOUT_PREFIX, GitCheckout, require_tool};
+pub use repair::ManifestRepair;
 pub use setup::Setup;
+pub use update::{UpdateStatus, UpdateTarget, Updater};
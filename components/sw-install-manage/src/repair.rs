@@ -0,0 +1,85 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use std::path::PathBuf;
+use sw_install_core::{InstallError, NormalOutput, Result, resolve_install_dir};
+use sw_install_installer::InstallLock;
+use sw_install_manifest::{Manifest, PruneEntry, RepairReport};
+
+/// Rebuilds the install manifest from the binaries actually on disk, and
+/// prunes entries that can no longer work.
+pub struct ManifestRepair<'a> {
+    dry_run: bool,
+    test_dir: Option<PathBuf>,
+    install_dir: Option<PathBuf>,
+    output: &'a NormalOutput,
+}
+
+impl<'a> ManifestRepair<'a> {
+    pub fn new(dry_run: bool, test_dir: Option<PathBuf>, output: &'a NormalOutput) -> Self {
+        Self {
+            dry_run,
+            test_dir,
+            install_dir: None,
+            output,
+        }
+    }
+
+    pub fn with_install_dir(mut self, install_dir: Option<PathBuf>) -> Self {
+        self.install_dir = install_dir;
+        self
+    }
+
+    pub fn repair(&self) -> Result<RepairReport> {
+        let dir = self.existing_dir()?;
+        self.output.info(&format!(
+            "Scanning {} for installed binaries...",
+            dir.display()
+        ));
+        let _lock = (!self.dry_run)
+            .then(|| InstallLock::acquire(&dir))
+            .transpose()?;
+        let mut manifest = Manifest::load(&dir, self.output)?;
+        let report = manifest.reconcile(&dir)?;
+        if !self.dry_run {
+            manifest.save(&dir)?;
+        }
+        self.output.success(&format!(
+            "Manifest repaired: {} kept, {} added, {} dropped",
+            report.kept.len(),
+            report.added.len(),
+            report.dropped.len()
+        ));
+        Ok(report)
+    }
+
+    /// Removes installed binaries whose source project is gone and dangling
+    /// symlinks, once `confirm` approves the list. Returns what was removed,
+    /// or under dry-run what would be.
+    pub fn prune(&self, confirm: impl FnOnce(&[PruneEntry]) -> bool) -> Result<Vec<PruneEntry>> {
+        let dir = self.existing_dir()?;
+        let _lock = (!self.dry_run)
+            .then(|| InstallLock::acquire(&dir))
+            .transpose()?;
+        let mut manifest = Manifest::load(&dir, self.output)?;
+        let entries = manifest.prunable(&dir)?;
+        if entries.is_empty() || self.dry_run {
+            return Ok(entries);
+        }
+        if !confirm(&entries) {
+            self.output.info("Prune cancelled");
+            return Ok(Vec::new());
+        }
+        manifest.prune(&dir, &entries)?;
+        manifest.save(&dir)?;
+        Ok(entries)
+    }
+
+    fn existing_dir(&self) -> Result<PathBuf> {
+        let dir = resolve_install_dir(self.install_dir.as_deref(), self.test_dir.as_deref())?;
+        if !dir.is_dir() {
+            return Err(InstallError::InstallDirNotFound(dir));
+        }
+        Ok(dir)
+    }
+}
@@ -1,15 +1,17 @@
 // Copyright (c) 2025 Michael A Wright
 // Licensed under the MIT License
 
-use crate::shell::{find_shell_config, write_path_config};
 use std::fs;
-use std::path::{Path, PathBuf};
-use sw_install_core::{InstallError, NormalOutput, Result};
+use std::path::PathBuf;
+use sw_install_core::{InstallError, NormalOutput, Result, StepStatus, resolve_install_dir};
 
 pub struct Setup<'a> {
-    dry_run: bool,
-    test_dir: Option<PathBuf>,
-    output: &'a NormalOutput,
+    pub(crate) dry_run: bool,
+    pub(crate) test_dir: Option<PathBuf>,
+    pub(crate) install_dir: Option<PathBuf>,
+    pub(crate) path_setup: bool,
+    pub(crate) shell: Option<String>,
+    pub(crate) output: &'a NormalOutput,
 }
 
 impl<'a> Setup<'a> {
@@ -17,60 +19,79 @@ impl<'a> Setup<'a> {
         Self {
             dry_run,
             test_dir,
+            install_dir: None,
+            path_setup: true,
+            shell: None,
             output,
         }
     }
 
+    pub fn with_install_dir(mut self, install_dir: Option<PathBuf>) -> Self {
+        self.install_dir = install_dir;
+        self
+    }
+
+    /// Disables editing the shell config; PATH is left to the user.
+    pub fn with_path_setup(mut self, path_setup: bool) -> Self {
+        self.path_setup = path_setup;
+        self
+    }
+
+    /// Configures the named shell's rc file instead of the detected one.
+    pub fn with_shell(mut self, shell: Option<String>) -> Self {
+        self.shell = shell;
+        self
+    }
+
     pub fn setup(&self) -> Result<()> {
         self.output.info("[1/3] Creating installation directory...");
+        self.output.step("create_dir", StepStatus::Start);
         let install_dir = self.create_install_dir()?;
+        self.output.step("create_dir", StepStatus::Done);
         self.output
             .info(&format!("Created: {}", install_dir.display()));
         self.output.info("[2/3] Detecting shell configuration...");
+        self.output.step("shell_config", StepStatus::Start);
         let shell_config = self.configure_shell(&install_dir)?;
+        self.output.step("shell_config", StepStatus::Done);
+        let next_step = if self.path_setup {
+            format!(
+                "To activate PATH changes, run:\n  source {}",
+                shell_config.display()
+            )
+        } else {
+            format!(
+                "Add it to PATH yourself:\n  export PATH=\"{}:$PATH\"",
+                install_dir.display()
+            )
+        };
         self.output.success(&format!(
-            "\nSetup complete!\n\nInstallation directory: {}\n\nTo activate PATH changes, run:\n  source {}",
-            install_dir.display(), shell_config.display()
+            "\nSetup complete!\n\nInstallation directory: {}\n\n{}",
+            install_dir.display(),
+            next_step
         ));
         Ok(())
     }
 
     pub(crate) fn create_install_dir(&self) -> Result<PathBuf> {
-        let install_dir = self.test_dir.clone().map_or_else(
-            || {
-                Ok(
-                    PathBuf::from(std::env::var("HOME").map_err(|_| InstallError::HomeNotFound)?)
-                        .join(".local/softwarewrighter/bin"),
-                )
-            },
-            Ok::<_, InstallError>,
-        )?;
+        let install_dir =
+            resolve_install_dir(self.install_dir.as_deref(), self.test_dir.as_deref())?;
+        if install_dir.exists() && !install_dir.is_dir() {
+            return Err(InstallError::InstallDirNotADirectory(install_dir));
+        }
         if !self.dry_run {
             fs::create_dir_all(&install_dir)?;
         }
         Ok(install_dir)
     }
-
-    pub(crate) fn configure_shell(&self, install_dir: &Path) -> Result<PathBuf> {
-        let home = std::env::var("HOME").map_err(|_| InstallError::HomeNotFound)?;
-        let shell_config = find_shell_config(Path::new(&home));
-        self.output
-            .info(&format!("Shell config: {}", shell_config.display()));
-        self.output
-            .info("[3/3] Adding PATH configuration to shell...");
-        if self.test_dir.is_some() {
-            self.output.info("Test mode: skipping shell configuration");
-            return Ok(shell_config);
-        }
-        write_path_config(&shell_config, install_dir, self.dry_run, self.output)
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use serial_test::serial;
-    use sw_install_core::NormalOutput;
+    use std::path::Path;
+    use sw_install_core::{InstallError, NormalOutput};
     use tempfile::TempDir;
 
     #[test]
@@ -114,6 +135,193 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_read_only_shell_config_reports_export_line() {
+        use std::os::unix::fs::PermissionsExt;
+        let home = TempDir::new().unwrap();
+        let rc = home.path().join(".bashrc");
+        fs::write(&rc, "# existing\n").unwrap();
+        fs::set_permissions(&rc, fs::Permissions::from_mode(0o444)).unwrap();
+        if fs::OpenOptions::new().append(true).open(&rc).is_ok() {
+            return; // Running as root: permission bits are not enforced
+        }
+        let bin = home.path().join("bin");
+        let output = NormalOutput::default();
+        let err = crate::shell::write_path_config(&rc, &bin, false, &output).unwrap_err();
+        assert!(matches!(err, InstallError::ShellConfigNotWritable(..)));
+        let message = err.to_string();
+        assert!(message.contains(&format!("export PATH=\"{}:$PATH\"", bin.display())));
+        assert!(message.contains("--no-path-setup"));
+    }
+
+    fn dry_run_messages(rc_contents: &str) -> Vec<String> {
+        let home = TempDir::new().unwrap();
+        let rc = home.path().join(".bashrc");
+        let bin = home.path().join("bin");
+        let line = format!("export PATH=\"{}:$PATH\"", bin.display());
+        fs::write(&rc, rc_contents.replace("{line}", &line)).unwrap();
+        let captured = sw_install_core::CapturedOutput::default();
+        let output = NormalOutput::new(true, true).with_handler(captured.clone());
+        crate::shell::write_path_config(&rc, &bin, true, &output).unwrap();
+        assert_eq!(
+            fs::read_to_string(&rc).unwrap(),
+            rc_contents.replace("{line}", &line)
+        );
+        captured.lines()
+    }
+
+    #[test]
+    fn test_dry_run_reports_already_configured_path() {
+        let lines = dry_run_messages("# Added by sw-install\n{line}\n");
+        assert_eq!(
+            lines,
+            vec!["Would: PATH already configured (no change needed)"]
+        );
+        let lines = dry_run_messages("# empty\n");
+        assert!(lines[0].starts_with("Would: Would add to "));
+    }
+
+    #[test]
+    fn test_refresh_rewrites_outdated_export_line() {
+        let home = TempDir::new().unwrap();
+        let rc = home.path().join(".bashrc");
+        let old_bin = home.path().join("old-bin");
+        let new_bin = home.path().join("new-bin");
+        let export = |dir: &Path| format!("export PATH=\"{}:$PATH\"", dir.display());
+        let before = format!(
+            "# mine\n# Added by sw-install\n{}\nalias ll=ls\n",
+            export(&old_bin)
+        );
+        fs::write(&rc, &before).unwrap();
+        let output = NormalOutput::default();
+
+        crate::shell::refresh_path_config(&rc, &new_bin, false, &output).unwrap();
+        let after = fs::read_to_string(&rc).unwrap();
+        let expected = format!(
+            "# mine\n# Added by sw-install\n{}\nalias ll=ls\n",
+            export(&new_bin)
+        );
+        assert_eq!(after, expected);
+
+        crate::shell::refresh_path_config(&rc, &new_bin, false, &output).unwrap();
+        assert_eq!(fs::read_to_string(&rc).unwrap(), expected);
+    }
+
+    #[test]
+    #[serial]
+    fn test_zsh_config_follows_zdotdir() {
+        let home = TempDir::new().unwrap();
+        let zdotdir = TempDir::new().unwrap();
+        fs::write(home.path().join(".zshrc"), "").unwrap();
+        let saved: Vec<_> = ["SHELL", "ZDOTDIR"]
+            .map(|k| (k, std::env::var_os(k)))
+            .into();
+        unsafe {
+            std::env::set_var("SHELL", "/bin/zsh");
+            std::env::set_var("ZDOTDIR", zdotdir.path());
+        }
+        let with_zdotdir = crate::shell::find_shell_config(home.path(), None);
+        unsafe { std::env::remove_var("ZDOTDIR") };
+        let without = crate::shell::find_shell_config(home.path(), None);
+        for (key, value) in saved {
+            match value {
+                Some(v) => unsafe { std::env::set_var(key, v) },
+                None => unsafe { std::env::remove_var(key) },
+            }
+        }
+        assert_eq!(with_zdotdir, zdotdir.path().join(".zshrc"));
+        assert_eq!(without, home.path().join(".zshrc"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_install_dir_already_on_path_skips_config_write() {
+        let home = TempDir::new().unwrap();
+        let bin = home.path().join("bin");
+        fs::create_dir_all(&bin).unwrap();
+        let saved: Vec<_> = ["HOME", "PATH", "SHELL"]
+            .map(|k| (k, std::env::var_os(k)))
+            .into();
+        unsafe {
+            std::env::set_var("HOME", home.path());
+            std::env::set_var("SHELL", "/bin/bash");
+            std::env::set_var("PATH", format!("/usr/bin:{}", bin.display()));
+        }
+        let output = NormalOutput::default();
+        let setup = Setup::new(false, None, &output).with_install_dir(Some(bin));
+        let result = setup.configure_shell(&home.path().join("bin"));
+        for (key, value) in saved {
+            match value {
+                Some(v) => unsafe { std::env::set_var(key, v) },
+                None => unsafe { std::env::remove_var(key) },
+            }
+        }
+        assert_eq!(result.unwrap(), home.path().join(".bashrc"));
+        assert!(!home.path().join(".bashrc").exists());
+    }
+
+    fn configure_with_shell(home: &Path, shell: &str) -> PathBuf {
+        let saved: Vec<_> = ["HOME", "PATH", "SHELL", "XDG_CONFIG_HOME"]
+            .map(|k| (k, std::env::var_os(k)))
+            .into();
+        unsafe {
+            std::env::set_var("HOME", home);
+            std::env::set_var("SHELL", "/bin/bash");
+            std::env::set_var("PATH", "/usr/bin");
+            std::env::remove_var("XDG_CONFIG_HOME");
+        }
+        let output = NormalOutput::default();
+        let setup = Setup::new(false, None, &output).with_shell(Some(shell.to_string()));
+        let result = setup.configure_shell(&home.join("bin"));
+        for (key, value) in saved {
+            match value {
+                Some(v) => unsafe { std::env::set_var(key, v) },
+                None => unsafe { std::env::remove_var(key) },
+            }
+        }
+        result.unwrap()
+    }
+
+    #[test]
+    #[serial]
+    fn test_forced_zsh_creates_zshrc_despite_bashrc() {
+        let home = TempDir::new().unwrap();
+        fs::write(home.path().join(".bashrc"), "# bash\n").unwrap();
+
+        let rc = configure_with_shell(home.path(), "zsh");
+        assert_eq!(rc, home.path().join(".zshrc"));
+        let bin = home.path().join("bin");
+        assert_eq!(
+            fs::read_to_string(&rc).unwrap(),
+            format!(
+                "\n# Added by sw-install\nexport PATH=\"{}:$PATH\"\n",
+                bin.display()
+            )
+        );
+        assert_eq!(
+            fs::read_to_string(home.path().join(".bashrc")).unwrap(),
+            "# bash\n"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_forced_fish_uses_fish_syntax() {
+        let home = TempDir::new().unwrap();
+
+        let rc = configure_with_shell(home.path(), "fish");
+        assert_eq!(rc, home.path().join(".config/fish/config.fish"));
+        let bin = home.path().join("bin");
+        assert_eq!(
+            fs::read_to_string(&rc).unwrap(),
+            format!(
+                "\n# Added by sw-install\nset -gx PATH \"{}\" $PATH\n",
+                bin.display()
+            )
+        );
+    }
+
     #[test]
     #[serial]
     fn test_full_setup_with_test_dir() {
@@ -2,36 +2,118 @@
 // Licensed under the MIT License
 
 use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
-use sw_install_core::{NormalOutput, Result};
+use sw_install_core::{InstallError, NormalOutput, Result};
 
-pub fn find_shell_config(home: &Path) -> PathBuf {
-    let shell = std::env::var("SHELL").unwrap_or_default();
-    let preferred: &[&str] = if shell.ends_with("zsh") {
-        &[".zshrc", ".zprofile"]
-    } else {
-        &[".bashrc", ".bash_profile", ".profile"]
+/// Comment line written above the export line sw-install manages.
+const MARKER: &str = "# Added by sw-install";
+
+/// The rc file for `shell` (bash, zsh, fish or sh), or for `$SHELL` when
+/// none is named. A named shell always gets its own rc file, even if only
+/// another shell's file exists yet.
+pub fn find_shell_config(home: &Path, shell: Option<&str>) -> PathBuf {
+    let detected = std::env::var("SHELL").unwrap_or_default();
+    let name = shell.unwrap_or(if detected.ends_with("zsh") { "zsh" } else { "" });
+    let dir = shell_dir(name, home);
+    let preferred: &[&str] = match name {
+        "zsh" => &[".zshrc", ".zprofile"],
+        "fish" => &["config.fish"],
+        "sh" => &[".profile"],
+        "bash" => &[".bashrc"],
+        _ => &[".bashrc", ".bash_profile", ".profile"],
     };
     preferred
         .iter()
-        .map(|f| home.join(f))
+        .map(|f| dir.join(f))
         .find(|p| p.exists())
-        .unwrap_or_else(|| home.join(preferred[0]))
+        .unwrap_or_else(|| dir.join(preferred[0]))
+}
+
+/// Where `shell` reads its startup files from.
+fn shell_dir(shell: &str, home: &Path) -> PathBuf {
+    let var = |key: &str| std::env::var_os(key).filter(|d| !d.is_empty());
+    match shell {
+        // zsh reads its startup files from $ZDOTDIR when set
+        "zsh" => var("ZDOTDIR").map_or_else(|| home.to_path_buf(), PathBuf::from),
+        "fish" => var("XDG_CONFIG_HOME")
+            .map_or_else(|| home.join(".config"), PathBuf::from)
+            .join("fish"),
+        _ => home.to_path_buf(),
+    }
+}
+
+/// The line that prepends `dir` to PATH, in fish syntax for fish configs.
+fn path_line(cfg: &Path, dir: &Path) -> String {
+    if cfg.extension().is_some_and(|e| e == "fish") {
+        format!("set -gx PATH \"{}\" $PATH", dir.display())
+    } else {
+        format!("export PATH=\"{}:$PATH\"", dir.display())
+    }
 }
 
 #[rustfmt::skip]
 pub fn write_path_config(cfg: &Path, dir: &Path, dry_run: bool, out: &NormalOutput) -> Result<PathBuf> {
-    let path_line = format!("export PATH=\"{}:$PATH\"", dir.display());
+    let path_line = path_line(cfg, dir);
+    let content = fs::read_to_string(cfg).unwrap_or_default();
+    if content.contains(&path_line) {
+        let note = if dry_run { "(no change needed)" } else { "in shell config" };
+        out.info(&format!("PATH already configured {note}"));
+        return Ok(cfg.to_path_buf());
+    }
     if dry_run {
         out.info(&format!("Would add to {}: {}", cfg.display(), path_line));
         return Ok(cfg.to_path_buf());
     }
+    if let Some(parent) = cfg.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let sep = if content.is_empty() || content.ends_with('\n') { "" } else { "\n" };
+    fs::write(cfg, format!("{}{}\n{}\n{}\n", content, sep, MARKER, path_line))
+        .map_err(|e| write_error(e, cfg, path_line))?;
+    Ok(cfg.to_path_buf())
+}
+
+/// Points an existing sw-install block at `dir` by rewriting the line after
+/// its marker; without a block, appends one as setup would.
+pub fn refresh_path_config(
+    cfg: &Path,
+    dir: &Path,
+    dry_run: bool,
+    out: &NormalOutput,
+) -> Result<PathBuf> {
+    let path_line = path_line(cfg, dir);
     let content = fs::read_to_string(cfg).unwrap_or_default();
-    if content.contains(&path_line) {
-        out.info("PATH already configured in shell config");
+    let mut lines: Vec<&str> = content.lines().collect();
+    let Some(marker) = lines.iter().position(|l| l.trim() == MARKER) else {
+        return write_path_config(cfg, dir, dry_run, out);
+    };
+    match lines.get(marker + 1) {
+        Some(line) if *line == path_line => {
+            out.info("PATH configuration is current (no change needed)");
+            return Ok(cfg.to_path_buf());
+        }
+        Some(line) if line.starts_with("export PATH=") || line.starts_with("set -gx PATH ") => {
+            lines[marker + 1] = &path_line
+        }
+        _ => lines.insert(marker + 1, &path_line),
+    }
+    if dry_run {
+        out.info(&format!("Would update {}: {}", cfg.display(), path_line));
         return Ok(cfg.to_path_buf());
     }
-    let sep = if content.is_empty() || content.ends_with('\n') { "" } else { "\n" };
-    fs::write(cfg, format!("{}{}\n# Added by sw-install\n{}\n", content, sep, path_line))?;
+    fs::write(cfg, format!("{}\n", lines.join("\n")))
+        .map_err(|e| write_error(e, cfg, path_line.clone()))?;
+    out.info(&format!("Updated {}: {}", cfg.display(), path_line));
     Ok(cfg.to_path_buf())
 }
+
+/// Turns a permission failure into an error that carries the manual fix.
+fn write_error(error: io::Error, cfg: &Path, path_line: String) -> InstallError {
+    match error.kind() {
+        io::ErrorKind::PermissionDenied | io::ErrorKind::ReadOnlyFilesystem => {
+            InstallError::ShellConfigNotWritable(cfg.to_path_buf(), path_line)
+        }
+        _ => InstallError::Io(error),
+    }
+}
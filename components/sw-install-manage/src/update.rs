@@ -0,0 +1,152 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use sw_install_core::{
+    InstallConfig, InstallError, InstallLayout, NormalOutput, Result, find_installed_binary,
+    resolve_install_dir,
+};
+use sw_install_manifest::{Manifest, ManifestEntry};
+use sw_install_validation::Validator;
+
+/// How an installed binary compares with its recorded source build.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateStatus {
+    Current,
+    Stale,
+    /// The comparison could not be made; carries the reason.
+    Unavailable(String),
+}
+
+/// A manifest-tracked binary and the config that reinstalls it from source.
+#[derive(Debug)]
+pub struct UpdateTarget {
+    pub name: String,
+    pub installed: Option<PathBuf>,
+    /// `None` when the manifest does not know the source project.
+    pub config: Option<InstallConfig>,
+}
+
+/// Resolves manifest-tracked binaries back to their source projects.
+pub struct Updater<'a> {
+    names: Vec<String>,
+    dry_run: bool,
+    verbose: bool,
+    test_dir: Option<PathBuf>,
+    install_dir: Option<PathBuf>,
+    output: &'a NormalOutput,
+}
+
+impl<'a> Updater<'a> {
+    /// `names` selects tracked binaries; empty means all of them.
+    pub fn new(
+        names: Vec<String>,
+        dry_run: bool,
+        test_dir: Option<PathBuf>,
+        output: &'a NormalOutput,
+    ) -> Self {
+        Self {
+            names,
+            dry_run,
+            verbose: false,
+            test_dir,
+            install_dir: None,
+            output,
+        }
+    }
+
+    pub fn with_install_dir(mut self, install_dir: Option<PathBuf>) -> Self {
+        self.install_dir = install_dir;
+        self
+    }
+
+    pub fn with_verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    /// Tracked binaries selected by name, in manifest order.
+    pub fn targets(&self) -> Result<Vec<UpdateTarget>> {
+        let dest_dir = resolve_install_dir(self.install_dir.as_deref(), self.test_dir.as_deref())?;
+        let manifest = Manifest::load(&dest_dir, self.output)?;
+        if let Some(missing) = self.names.iter().find(|n| manifest.get(n).is_none()) {
+            return Err(InstallError::BinaryNotInstalled(missing.clone()));
+        }
+        Ok(manifest
+            .entries()
+            .filter(|e| self.names.is_empty() || self.names.contains(&e.name))
+            .map(|e| self.target(&dest_dir, e))
+            .collect())
+    }
+
+    /// Compares every selected binary with its source build, read-only.
+    pub fn check(&self) -> Result<Vec<(String, UpdateStatus)>> {
+        self.output
+            .info("Checking tracked binaries against their sources...");
+        Ok(self
+            .targets()?
+            .into_iter()
+            .map(|t| {
+                let status = t.status();
+                (t.name, status)
+            })
+            .collect())
+    }
+
+    fn target(&self, dest_dir: &Path, entry: &ManifestEntry) -> UpdateTarget {
+        let installed = find_installed_binary(dest_dir, &entry.name);
+        let config = entry.source.as_ref().map(|source| {
+            let binary = entry.binary.clone().unwrap_or_else(|| entry.name.clone());
+            let rename = entry.binary.as_ref().map(|_| entry.name.clone());
+            let mut config = InstallConfig::new(
+                source.clone(),
+                rename,
+                vec![binary],
+                entry.debug,
+                self.verbose,
+                self.dry_run,
+                false,
+                self.test_dir.clone(),
+            );
+            config.install_dir = self.install_dir.clone();
+            let nested = installed
+                .as_ref()
+                .is_some_and(|p| p.parent() != Some(dest_dir));
+            if nested {
+                config.layout = InstallLayout::Nested;
+            }
+            config
+        });
+        UpdateTarget {
+            name: entry.name.clone(),
+            installed,
+            config,
+        }
+    }
+}
+
+impl UpdateTarget {
+    /// Whether the source build differs from what is installed.
+    pub fn status(&self) -> UpdateStatus {
+        let Some(ref config) = self.config else {
+            return unavailable("source project unknown");
+        };
+        let Some(ref installed) = self.installed else {
+            return unavailable("installed binary missing");
+        };
+        let source = match Validator::new(config, &NormalOutput::quiet()).validate() {
+            Ok(result) => result.binaries[0].1.clone(),
+            Err(e) => return unavailable(e.to_string().lines().next().unwrap_or_default()),
+        };
+        match (fs::read(&source), fs::read(installed)) {
+            (Ok(new), Ok(old)) if new == old => UpdateStatus::Current,
+            (Ok(_), Ok(_)) => UpdateStatus::Stale,
+            (Err(e), _) | (_, Err(e)) => unavailable(&e.to_string()),
+        }
+    }
+}
+
+fn unavailable(reason: &str) -> UpdateStatus {
+    UpdateStatus::Unavailable(reason.to_string())
+}
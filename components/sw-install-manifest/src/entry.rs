@@ -0,0 +1,90 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// What sw-install knows about one installed binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestEntry {
+    pub name: String,
+    /// Project the binary was installed from; `None` when unknown.
+    pub source: Option<PathBuf>,
+    /// Install time in seconds since the Unix epoch.
+    pub installed_at: u64,
+    /// Whether the binary came from a debug build.
+    pub debug: bool,
+    /// Cargo binary name when it was installed under a different name.
+    pub binary: Option<String>,
+    /// Cargo target triple the binary was built for, when not the host's.
+    pub target_triple: Option<String>,
+}
+
+impl ManifestEntry {
+    pub fn new(name: &str, source: Option<PathBuf>, installed_at: SystemTime) -> Self {
+        let installed_at = installed_at
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Self {
+            name: name.to_string(),
+            source,
+            installed_at,
+            debug: false,
+            binary: None,
+            target_triple: None,
+        }
+    }
+
+    pub(crate) fn to_table(&self) -> toml::Table {
+        let mut table = toml::Table::new();
+        if let Some(ref source) = self.source {
+            let source = source.display().to_string();
+            table.insert("source".into(), toml::Value::String(source));
+        }
+        let installed_at = i64::try_from(self.installed_at).unwrap_or(i64::MAX);
+        table.insert("installed_at".into(), toml::Value::Integer(installed_at));
+        if self.debug {
+            table.insert("debug".into(), toml::Value::Boolean(true));
+        }
+        if let Some(ref binary) = self.binary {
+            table.insert("binary".into(), toml::Value::String(binary.clone()));
+        }
+        if let Some(ref triple) = self.target_triple {
+            table.insert("target_triple".into(), toml::Value::String(triple.clone()));
+        }
+        table
+    }
+
+    pub(crate) fn from_value(name: &str, value: &toml::Value) -> Self {
+        let source = value
+            .get("source")
+            .and_then(|s| s.as_str())
+            .map(PathBuf::from);
+        let installed_at = value
+            .get("installed_at")
+            .and_then(|t| t.as_integer())
+            .and_then(|t| u64::try_from(t).ok())
+            .unwrap_or(0);
+        let debug = value
+            .get("debug")
+            .and_then(|d| d.as_bool())
+            .unwrap_or(false);
+        let binary = value
+            .get("binary")
+            .and_then(|b| b.as_str())
+            .map(String::from);
+        let target_triple = value
+            .get("target_triple")
+            .and_then(|t| t.as_str())
+            .map(String::from);
+        Self {
+            name: name.to_string(),
+            source,
+            installed_at,
+            debug,
+            binary,
+            target_triple,
+        }
+    }
+}
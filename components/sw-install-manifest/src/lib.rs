@@ -0,0 +1,18 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+//! Install manifest and tool registry tracking for sw-install.
+
+mod entry;
+mod manifest;
+mod prune;
+mod reconcile;
+mod registry;
+mod store;
+
+pub use entry::ManifestEntry;
+pub use manifest::{CORRUPT_SUFFIX, MANIFEST_FILE_NAME, Manifest};
+pub use prune::{PruneEntry, PruneReason};
+pub use reconcile::RepairReport;
+pub use registry::ToolRegistry;
+pub use store::{FileManifestStore, ManifestStore, MemoryManifestStore};
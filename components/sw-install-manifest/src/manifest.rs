@@ -0,0 +1,107 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use crate::entry::ManifestEntry;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use sw_install_core::{InstallError, IoResultExt, NormalOutput, Result};
+
+pub const MANIFEST_FILE_NAME: &str = ".sw-install-manifest.toml";
+
+/// Appended to a manifest that failed to parse when it is moved aside.
+pub const CORRUPT_SUFFIX: &str = ".corrupt";
+
+/// Record of installed binaries, stored alongside them in the install dir.
+#[derive(Debug, Default, Clone)]
+pub struct Manifest {
+    entries: BTreeMap<String, ManifestEntry>,
+}
+
+impl Manifest {
+    /// Loads the manifest in `dir`; a missing file yields an empty manifest,
+    /// and so does a corrupt one, after it is set aside with a warning on
+    /// `output` so installs, listings and uninstalls keep working. Any other
+    /// read error fails, so a later save cannot overwrite the records.
+    pub fn load(dir: &Path, output: &NormalOutput) -> Result<Self> {
+        let path = manifest_path(dir);
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).io_context("reading", &path),
+        };
+        let value: toml::Table = match toml::from_str(&contents) {
+            Ok(value) => value,
+            Err(e) => {
+                set_aside_corrupt(&path, &e.to_string(), output);
+                return Ok(Self::default());
+            }
+        };
+        let entries = value
+            .get("binaries")
+            .and_then(|b| b.as_table())
+            .map(|bins| {
+                bins.iter()
+                    .map(|(name, v)| (name.clone(), ManifestEntry::from_value(name, v)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(Self { entries })
+    }
+
+    /// Writes entries sorted by binary name, so the file is byte-stable
+    /// across runs and diffs cleanly if committed.
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        let binaries: toml::Table = self
+            .entries
+            .iter()
+            .map(|(name, e)| (name.clone(), toml::Value::Table(e.to_table())))
+            .collect();
+        let mut root = toml::Table::new();
+        root.insert("binaries".into(), toml::Value::Table(binaries));
+        let contents =
+            toml::to_string(&root).map_err(|e| InstallError::ManifestParse(e.to_string()))?;
+        fs::write(
+            manifest_path(dir),
+            format!("# Managed by sw-install\n{contents}"),
+        )?;
+        Ok(())
+    }
+
+    pub fn record(&mut self, entry: ManifestEntry) {
+        self.entries.insert(entry.name.clone(), entry);
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<ManifestEntry> {
+        self.entries.remove(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ManifestEntry> {
+        self.entries.get(name)
+    }
+
+    /// Entries sorted by binary name.
+    pub fn entries(&self) -> impl Iterator<Item = &ManifestEntry> {
+        self.entries.values()
+    }
+}
+
+fn manifest_path(dir: &Path) -> PathBuf {
+    dir.join(MANIFEST_FILE_NAME)
+}
+
+/// Renames an unparseable manifest to `<name>.corrupt`, keeping it for
+/// inspection while the next save starts a fresh one.
+fn set_aside_corrupt(path: &Path, error: &str, output: &NormalOutput) {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(CORRUPT_SUFFIX);
+    let kept = match fs::rename(path, &backup) {
+        Ok(()) => format!("moved to {}", Path::new(&backup).display()),
+        Err(e) => format!("could not be moved aside: {e}"),
+    };
+    output.warn(&format!(
+        "Install manifest {} is corrupt ({error}); starting a new one, old file {kept}",
+        path.display()
+    ));
+}
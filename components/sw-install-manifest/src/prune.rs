@@ -0,0 +1,97 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use crate::manifest::Manifest;
+use std::fs;
+use std::path::{Path, PathBuf};
+use sw_install_core::{InstallLayout, Result, find_installed_binary, is_backup_name};
+
+/// An installed entry `--prune` removes, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PruneEntry {
+    pub name: String,
+    pub path: PathBuf,
+    pub reason: PruneReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PruneReason {
+    /// The project the manifest records it from no longer exists.
+    SourceGone(PathBuf),
+    /// A symlink whose target is gone.
+    DanglingLink,
+}
+
+impl std::fmt::Display for PruneReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SourceGone(source) => write!(f, "source {} no longer exists", source.display()),
+            Self::DanglingLink => write!(f, "dangling symlink"),
+        }
+    }
+}
+
+impl Manifest {
+    /// Installed binaries in `dir` whose recorded source project is gone,
+    /// followed by dangling symlinks, each listed once.
+    pub fn prunable(&self, dir: &Path) -> Result<Vec<PruneEntry>> {
+        let mut found: Vec<PruneEntry> = self
+            .entries()
+            .filter_map(|e| {
+                let source = e.source.as_ref().filter(|s| !s.exists())?;
+                Some(PruneEntry {
+                    name: e.name.clone(),
+                    path: find_installed_binary(dir, &e.name)?,
+                    reason: PruneReason::SourceGone(source.clone()),
+                })
+            })
+            .collect();
+        for link in dangling_links(dir)? {
+            if !found.iter().any(|f| f.name == link.name) {
+                found.push(link);
+            }
+        }
+        Ok(found)
+    }
+
+    /// Deletes each entry's file, and its directory under the nested
+    /// layout, and forgets it.
+    pub fn prune(&mut self, dir: &Path, entries: &[PruneEntry]) -> Result<()> {
+        for entry in entries {
+            fs::remove_file(&entry.path)?;
+            if let Some(parent) = entry.path.parent()
+                && parent != dir
+            {
+                // Leave the nested dir alone if anything else lives in it
+                let _ = fs::remove_dir(parent);
+            }
+            self.remove(&entry.name);
+        }
+        Ok(())
+    }
+}
+
+fn dangling_links(dir: &Path) -> Result<Vec<PruneEntry>> {
+    let mut links: Vec<PruneEntry> = fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().to_str()?.to_string();
+            if name.starts_with('.') || is_backup_name(&name) {
+                return None;
+            }
+            let is_dir = e.file_type().is_ok_and(|t| t.is_dir());
+            let path = match is_dir {
+                true => InstallLayout::Nested.binary_path(dir, &name),
+                false => e.path(),
+            };
+            let is_link = fs::symlink_metadata(&path).is_ok_and(|m| m.file_type().is_symlink());
+            (is_link && !path.exists()).then_some(PruneEntry {
+                name,
+                path,
+                reason: PruneReason::DanglingLink,
+            })
+        })
+        .collect();
+    links.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(links)
+}
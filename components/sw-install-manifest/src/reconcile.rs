@@ -0,0 +1,58 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use crate::entry::ManifestEntry;
+use crate::manifest::Manifest;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+use sw_install_core::{Result, installed_binaries};
+
+/// Outcome of reconciling the manifest with the install dir contents.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RepairReport {
+    pub kept: Vec<String>,
+    pub added: Vec<String>,
+    pub dropped: Vec<String>,
+}
+
+impl Manifest {
+    /// Makes the manifest match the binaries present in `dir`.
+    ///
+    /// Binaries without an entry get a minimal one (mtime, unknown source);
+    /// entries whose binary is gone are dropped.
+    pub fn reconcile(&mut self, dir: &Path) -> Result<RepairReport> {
+        let mut report = RepairReport::default();
+        let on_disk = installed_files(dir)?;
+        let stale: Vec<String> = self
+            .entries()
+            .filter(|e| !on_disk.iter().any(|(name, _)| *name == e.name))
+            .map(|e| e.name.clone())
+            .collect();
+        for name in stale {
+            self.remove(&name);
+            report.dropped.push(name);
+        }
+        for (name, modified) in on_disk {
+            if self.get(&name).is_some() {
+                report.kept.push(name);
+            } else {
+                self.record(ManifestEntry::new(&name, None, modified));
+                report.added.push(name);
+            }
+        }
+        Ok(report)
+    }
+}
+
+fn installed_files(dir: &Path) -> Result<Vec<(String, SystemTime)>> {
+    let mut files: Vec<(String, SystemTime)> = installed_binaries(dir)?
+        .into_iter()
+        .filter_map(|(name, path)| {
+            let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+            Some((name, modified))
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
@@ -0,0 +1,78 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use sw_install_core::{InstallError, Result};
+
+/// User-maintained aliases for projects, so `--tool NAME` can stand in for
+/// `--project PATH`.
+#[derive(Debug, Default)]
+pub struct ToolRegistry {
+    tools: BTreeMap<String, PathBuf>,
+}
+
+impl ToolRegistry {
+    /// `$XDG_CONFIG_HOME/sw-install/tools.toml`, falling back to `~/.config`.
+    pub fn default_path() -> Result<PathBuf> {
+        let config_home = match std::env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => {
+                let home = std::env::var("HOME").map_err(|_| InstallError::HomeNotFound)?;
+                PathBuf::from(home).join(".config")
+            }
+        };
+        Ok(config_home.join("sw-install").join("tools.toml"))
+    }
+
+    /// Loads the registry at `path`; a missing file yields an empty registry.
+    pub fn load(path: &Path) -> Result<Self> {
+        let Ok(contents) = fs::read_to_string(path) else {
+            return Ok(Self::default());
+        };
+        let value: toml::Table =
+            toml::from_str(&contents).map_err(|e| InstallError::RegistryParse(e.to_string()))?;
+        let tools = value
+            .get("tools")
+            .and_then(|t| t.as_table())
+            .map(|tools| {
+                tools
+                    .iter()
+                    .filter_map(|(name, v)| Some((name.clone(), PathBuf::from(v.as_str()?))))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(Self { tools })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let tools: toml::Table = self
+            .tools
+            .iter()
+            .map(|(name, p)| (name.clone(), toml::Value::String(p.display().to_string())))
+            .collect();
+        let mut root = toml::Table::new();
+        root.insert("tools".into(), toml::Value::Table(tools));
+        let contents =
+            toml::to_string(&root).map_err(|e| InstallError::RegistryParse(e.to_string()))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Maps `name` to `project`, replacing any previous registration.
+    pub fn register(&mut self, name: &str, project: PathBuf) {
+        self.tools.insert(name.to_string(), project);
+    }
+
+    pub fn unregister(&mut self, name: &str) -> Option<PathBuf> {
+        self.tools.remove(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Path> {
+        self.tools.get(name).map(PathBuf::as_path)
+    }
+}
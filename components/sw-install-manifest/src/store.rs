@@ -0,0 +1,92 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use crate::entry::ManifestEntry;
+use crate::manifest::Manifest;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use sw_install_core::{NormalOutput, Result};
+
+/// Where install records live. Installs, uninstalls and listings go
+/// through this, so tests can swap the on-disk manifest for memory.
+pub trait ManifestStore: Send + Sync {
+    /// Current records; an absent store yields an empty manifest.
+    fn load(&self) -> Result<Manifest>;
+
+    /// Adds or replaces the record for `entry.name`.
+    fn record(&self, entry: ManifestEntry) -> Result<()>;
+
+    /// Drops the record for `name`, returning it if there was one.
+    fn remove(&self, name: &str) -> Result<Option<ManifestEntry>>;
+
+    /// Every record, sorted by binary name.
+    fn all(&self) -> Result<Vec<ManifestEntry>> {
+        Ok(self.load()?.entries().cloned().collect())
+    }
+}
+
+/// The `.sw-install-manifest.toml` file in an install dir; the default.
+/// Warnings about a corrupt file go to `output`.
+pub struct FileManifestStore<'a> {
+    dir: PathBuf,
+    output: &'a NormalOutput,
+}
+
+impl<'a> FileManifestStore<'a> {
+    pub fn new(dir: &Path, output: &'a NormalOutput) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            output,
+        }
+    }
+}
+
+impl ManifestStore for FileManifestStore<'_> {
+    fn load(&self) -> Result<Manifest> {
+        Manifest::load(&self.dir, self.output)
+    }
+
+    fn record(&self, entry: ManifestEntry) -> Result<()> {
+        let mut manifest = self.load()?;
+        manifest.record(entry);
+        manifest.save(&self.dir)
+    }
+
+    fn remove(&self, name: &str) -> Result<Option<ManifestEntry>> {
+        let mut manifest = self.load()?;
+        let removed = manifest.remove(name);
+        if removed.is_some() {
+            manifest.save(&self.dir)?;
+        }
+        Ok(removed)
+    }
+}
+
+/// Records kept in memory only; clones share the same records, so keep
+/// one clone to inspect what an operation stored through the other.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryManifestStore {
+    manifest: Arc<Mutex<Manifest>>,
+}
+
+impl MemoryManifestStore {
+    fn lock(&self) -> MutexGuard<'_, Manifest> {
+        // A panic mid-update cannot leave a BTreeMap half-written
+        self.manifest.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl ManifestStore for MemoryManifestStore {
+    fn load(&self) -> Result<Manifest> {
+        Ok(self.lock().clone())
+    }
+
+    fn record(&self, entry: ManifestEntry) -> Result<()> {
+        self.lock().record(entry);
+        Ok(())
+    }
+
+    fn remove(&self, name: &str) -> Result<Option<ManifestEntry>> {
+        Ok(self.lock().remove(name))
+    }
+}
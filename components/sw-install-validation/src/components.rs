@@ -0,0 +1,108 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use crate::{ProjectType, Validator};
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use sw_install_core::{InstallError, Result};
+
+const DEFAULT_COMPONENTS_DIR: &str = "components";
+
+pub(crate) fn component_dirs(validator: &Validator) -> Vec<PathBuf> {
+    let project_path = &validator.config.project_path;
+    let mut dirs = Vec::new();
+    if let Some(ref name) = validator.config.components_dir {
+        dirs.push(project_path.join(name));
+    }
+    dirs.push(project_path.join(DEFAULT_COMPONENTS_DIR));
+    dirs
+}
+
+pub(crate) fn try_detect_multi_component(validator: &Validator) -> Option<ProjectType> {
+    let project_name = validator.config.project_path.file_name();
+    let component_path = component_dirs(validator)
+        .iter()
+        .find_map(|dir| find_binary_component(dir, project_name))?;
+    validator.output.info("Project type: multi-component");
+    Some(ProjectType::MultiComponent { component_path })
+}
+
+pub(crate) fn select_named_component(validator: &Validator, name: &str) -> Result<ProjectType> {
+    let component_path = component_dirs(validator)
+        .into_iter()
+        .map(|dir| dir.join(name))
+        .find(|path| path.is_dir())
+        .ok_or_else(|| InstallError::ComponentNotFound(name.to_string()))?;
+    if !is_valid_component(&component_path) {
+        return Err(InstallError::ComponentHasNoBinary(component_path));
+    }
+    validator
+        .output
+        .info(&format!("Project type: multi-component ({name})"));
+    Ok(ProjectType::MultiComponent { component_path })
+}
+
+/// Under strict detection, refuses to choose between several binary-bearing
+/// components in the first components dir that has any.
+pub(crate) fn check_single_component(validator: &Validator) -> Result<()> {
+    let found = component_dirs(validator)
+        .iter()
+        .map(|dir| binary_components(dir))
+        .find(|found| !found.is_empty())
+        .unwrap_or_default();
+    if found.len() < 2 {
+        return Ok(());
+    }
+    let names: Vec<String> = found
+        .iter()
+        .filter_map(|p| p.file_name())
+        .map(|n| n.to_string_lossy().into_owned())
+        .collect();
+    Err(InstallError::AmbiguousComponent(names.join(", ")))
+}
+
+/// Picks the component named after the project if it has a binary, otherwise
+/// the alphabetically-first binary-bearing component, so the choice does not
+/// depend on filesystem iteration order.
+fn find_binary_component(components: &Path, project_name: Option<&OsStr>) -> Option<PathBuf> {
+    let mut candidates = binary_components(components);
+    match candidates
+        .iter()
+        .position(|p| p.file_name() == project_name)
+    {
+        Some(index) => Some(candidates.swap_remove(index)),
+        None => candidates.into_iter().next(),
+    }
+}
+
+/// Binary-bearing components under `components`, sorted.
+fn binary_components(components: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(components) else {
+        return Vec::new();
+    };
+    let mut found: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|path| is_valid_component(path))
+        .collect();
+    found.sort();
+    found
+}
+
+fn is_valid_component(path: &Path) -> bool {
+    let Ok(contents) = fs::read_to_string(path.join("Cargo.toml")) else {
+        return false;
+    };
+    let Ok(value) = toml::from_str::<toml::Value>(&contents) else {
+        return false;
+    };
+    // Check for workspace with binaries
+    if let Some(ws) = value.get("workspace")
+        && let Some(members) = ws.get("members").and_then(|m| m.as_array())
+    {
+        return !sw_install_workspace::find_workspace_binaries(path, members).is_empty();
+    }
+    // Check for simple package with binary
+    value.get("package").is_some() && value.get("bin").is_some()
+}
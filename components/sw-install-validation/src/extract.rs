@@ -15,29 +15,70 @@ pub(crate) fn extract_binary_names(
         fs::read_to_string(&cargo_toml).map_err(|e| InstallError::CargoTomlParse(e.to_string()))?;
     let value: toml::Value =
         toml::from_str(&contents).map_err(|e| InstallError::CargoTomlParse(e.to_string()))?;
-    try_extract_from_workspace(&cargo_toml, &value)
-        .or_else(|| try_extract_from_bin(&value))
-        .or_else(|| try_extract_from_package(&value))
-        .ok_or(InstallError::BinaryNameNotFound)
+    if value.get("workspace").is_some() {
+        // Workspace roots only count as binaries via package_binaries, so a
+        // library root is never mistaken for an installable binary
+        return try_extract_from_workspace(validator, &cargo_toml, &value);
+    }
+    let dir = cargo_toml.parent().unwrap_or(Path::new("."));
+    let auto = sw_install_workspace::auto_bin_names(dir, &value);
+    // With src/bin/ targets the package name is only a binary if src/main.rs is
+    let has_main = auto.is_empty() || dir.join("src").join("main.rs").exists();
+    let mut names = try_extract_from_bin(&value)
+        .or_else(|| try_extract_from_package(&value).filter(|_| has_main))
+        .unwrap_or_default();
+    sw_install_workspace::merge_names(&mut names, auto);
+    if names.is_empty() {
+        return Err(InstallError::BinaryNameNotFound);
+    }
+    Ok(names)
 }
 
 fn get_cargo_toml_path(validator: &Validator, project_type: &ProjectType) -> std::path::PathBuf {
     match project_type {
-        ProjectType::Simple | ProjectType::Workspace => {
+        ProjectType::Simple | ProjectType::Workspace | ProjectType::WorkspaceMember { .. } => {
             validator.config.project_path.join("Cargo.toml")
         }
         ProjectType::MultiComponent { component_path } => component_path.join("Cargo.toml"),
     }
 }
 
-fn try_extract_from_workspace(cargo_toml: &Path, value: &toml::Value) -> Option<Vec<String>> {
-    let ws = value.get("workspace")?;
-    let members = ws.get("members").and_then(|m| m.as_array())?;
-    let binaries = sw_install_workspace::find_workspace_binaries(cargo_toml.parent()?, members);
-    if binaries.is_empty() {
-        return None;
+fn try_extract_from_workspace(
+    validator: &Validator,
+    cargo_toml: &Path,
+    value: &toml::Value,
+) -> Result<Vec<String>> {
+    let root = cargo_toml.parent().unwrap_or(Path::new("."));
+    let members = value
+        .get("workspace")
+        .and_then(|ws| ws.get("members"))
+        .and_then(|m| m.as_array());
+    let scan = sw_install_workspace::scan_workspace(root, members.map_or(&[], |m| m));
+    validator.output.info(&scan.summary());
+    check_duplicate_binaries(&scan)?;
+    if !scan.binaries.is_empty() {
+        return Ok(scan.binaries);
     }
-    Some(binaries)
+    // A root that is both [workspace] and [package] may itself be the binary
+    let root_binaries =
+        sw_install_workspace::package_binaries(root).ok_or(InstallError::BinaryNameNotFound)?;
+    validator
+        .output
+        .info("No binary members; using the workspace root package");
+    Ok(root_binaries)
+}
+
+/// Two members building the same binary would install to the same path,
+/// so neither can be chosen on the user's behalf.
+fn check_duplicate_binaries(scan: &sw_install_workspace::WorkspaceScan) -> Result<()> {
+    let Some((name, paths)) = scan.duplicates.first() else {
+        return Ok(());
+    };
+    let paths: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+    Err(InstallError::DuplicateWorkspaceBinary(
+        name.clone(),
+        paths.join(", "),
+    ))
 }
 
 fn try_extract_from_bin(value: &toml::Value) -> Option<Vec<String>> {
@@ -3,12 +3,19 @@
 
 //! Project validation for sw-install.
 
+mod components;
 mod detect;
 mod extract;
+mod profile;
+mod report;
 mod source;
 
-use std::path::PathBuf;
-use sw_install_core::{InstallConfig, InstallError, NormalOutput, Result};
+pub use report::DetectionReport;
+
+use std::path::{Path, PathBuf};
+use sw_install_core::{
+    InstallConfig, InstallError, NormalOutput, Result, StepStatus, check_binary_name,
+};
 
 #[derive(Debug)]
 pub struct ValidationResult {
@@ -20,16 +27,33 @@ pub struct ValidationResult {
 pub(crate) enum ProjectType {
     Simple,
     Workspace,
-    MultiComponent { component_path: PathBuf },
+    MultiComponent {
+        component_path: PathBuf,
+    },
+    /// A member crate given directly, whose binaries cargo writes to the
+    /// enclosing workspace's `target/`.
+    WorkspaceMember {
+        workspace_root: PathBuf,
+    },
 }
 
 impl ProjectType {
-    pub(crate) fn build_dir(&self, project_path: &std::path::Path) -> PathBuf {
+    pub(crate) fn build_dir(&self, project_path: &Path) -> PathBuf {
         match self {
-            Self::Simple | Self::Workspace => project_path.to_path_buf(),
+            Self::Simple | Self::Workspace | Self::WorkspaceMember { .. } => {
+                project_path.to_path_buf()
+            }
             Self::MultiComponent { component_path } => component_path.clone(),
         }
     }
+
+    /// Directory whose `target/` holds the built binaries.
+    pub(crate) fn target_root(&self, project_path: &Path) -> PathBuf {
+        match self {
+            Self::WorkspaceMember { workspace_root } => workspace_root.clone(),
+            _ => self.build_dir(project_path),
+        }
+    }
 }
 
 pub struct Validator<'a> {
@@ -49,18 +73,20 @@ impl<'a> Validator<'a> {
     }
 
     pub fn validate(&self) -> Result<ValidationResult> {
+        self.output.step("validate", StepStatus::Start);
         self.output.info("[1/4] Validating project path...");
         self.validate_path()?;
         self.output.info("[2/4] Detecting project structure...");
         let project_type = detect::detect_project_type(self)?;
         let build_dir = project_type.build_dir(&self.config.project_path);
         self.output.info("[3/4] Extracting binary names...");
-        let names = extract::extract_binary_names(self, &project_type)?;
-        let filtered = self.apply_bin_filter(names)?;
+        let filtered = self.resolve_names(&project_type)?;
         self.output
             .info(&format!("Binaries: {}", filtered.join(", ")));
+        self.check_profile_built(&project_type.target_root(&self.config.project_path))?;
         self.output.info("[4/4] Verifying source binaries exist...");
         let binaries = source::validate_source_binaries(self, &filtered, &project_type)?;
+        self.output.step("validate", StepStatus::Done);
         self.output.success("Validation complete");
         Ok(ValidationResult {
             binaries,
@@ -68,8 +94,20 @@ impl<'a> Validator<'a> {
         })
     }
 
+    fn resolve_names(&self, project_type: &ProjectType) -> Result<Vec<String>> {
+        if let Some(ref example) = self.config.example {
+            return Ok(vec![example.clone()]);
+        }
+        let names = extract::extract_binary_names(self, project_type)?;
+        names.iter().try_for_each(|name| check_binary_name(name))?;
+        self.apply_bin_filter(names)
+    }
+
     fn apply_bin_filter(&self, names: Vec<String>) -> Result<Vec<String>> {
         if self.config.bin_filter.is_empty() {
+            if self.config.strict_detection && !self.config.install_all && names.len() > 1 {
+                return Err(InstallError::AmbiguousBinaries(names.join(", ")));
+            }
             return Ok(names);
         }
         for name in &self.config.bin_filter {
@@ -80,6 +118,20 @@ impl<'a> Validator<'a> {
         Ok(self.config.bin_filter.clone())
     }
 
+    /// Fails fast when no build of the selected profile exists at all,
+    /// before any per-binary lookup.
+    fn check_profile_built(&self, target_root: &Path) -> Result<()> {
+        let profile = self.config.profile();
+        let profile_dir = target_root.join("target").join(profile);
+        if profile_dir.is_dir() {
+            return Ok(());
+        }
+        Err(InstallError::ProfileNotBuilt(
+            profile.to_string(),
+            profile_dir,
+        ))
+    }
+
     fn validate_path(&self) -> Result<()> {
         if !self.config.project_path.exists() {
             return Err(InstallError::ProjectNotFound(
@@ -0,0 +1,57 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use crate::Validator;
+use std::fs;
+use std::time::SystemTime;
+use sw_install_core::{NormalOutput, Result};
+
+impl Validator<'_> {
+    /// Under `auto_profile`, picks the build holding every selected binary:
+    /// whichever of release or debug has them all, or the newer when both
+    /// do. Returns `use_debug` for that build, or `None` to keep the
+    /// configured profile and let validation report what is missing.
+    pub fn auto_profile(&self) -> Result<Option<bool>> {
+        let release = self.newest_binary(false)?;
+        let debug = self.newest_binary(true)?;
+        let use_debug = match (release, debug) {
+            (Some(release), Some(debug)) => debug > release,
+            (None, Some(_)) => true,
+            (Some(_), None) => false,
+            (None, None) => return Ok(None),
+        };
+        if use_debug != self.config.use_debug {
+            let (chosen, other) = if use_debug {
+                ("debug", "release")
+            } else {
+                ("release", "debug")
+            };
+            let reason = if release.is_some() && debug.is_some() {
+                "is newer"
+            } else {
+                "is the only one built"
+            };
+            self.output.warn(&format!(
+                "Using the {chosen} build, which {reason}; pass --type {other} to override"
+            ));
+        }
+        Ok(Some(use_debug))
+    }
+
+    /// Latest mtime among the selected binaries in one profile, or `None`
+    /// when any of them is missing there.
+    fn newest_binary(&self, use_debug: bool) -> Result<Option<SystemTime>> {
+        let mut config = self.config.clone();
+        config.use_debug = use_debug;
+        // Probe quietly; validation repeats any detection messages
+        let report = Validator::new(&config, &NormalOutput::quiet()).detect()?;
+        let mut newest = None;
+        for (_, path) in &report.binaries {
+            let Ok(modified) = fs::metadata(path).and_then(|m| m.modified()) else {
+                return Ok(None);
+            };
+            newest = newest.max(Some(modified));
+        }
+        Ok(newest)
+    }
+}
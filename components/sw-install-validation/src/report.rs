@@ -0,0 +1,69 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use crate::{ProjectType, Validator, detect, source};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use sw_install_core::Result;
+
+/// What detection decided about a project, reported by `--detect` without
+/// checking that anything has been built.
+#[derive(Debug)]
+pub struct DetectionReport {
+    /// `simple`, `workspace`, `workspace-member` or `multi-component`.
+    pub project_type: &'static str,
+    /// The chosen component directory, for multi-component projects.
+    pub component: Option<PathBuf>,
+    /// Binary names after `--bin` filtering, with the path each would be copied from.
+    pub binaries: Vec<(String, PathBuf)>,
+    pub build_dir: PathBuf,
+}
+
+impl Validator<'_> {
+    /// Runs only project detection and binary name extraction.
+    pub fn detect(&self) -> Result<DetectionReport> {
+        self.validate_path()?;
+        let project_type = detect::detect_project_type(self)?;
+        let binaries = self
+            .resolve_names(&project_type)?
+            .into_iter()
+            .map(|name| {
+                let (source_path, _) = source::get_source_paths(self, &name, &project_type);
+                (name, source_path)
+            })
+            .collect();
+        let (label, component) = match &project_type {
+            ProjectType::Simple => ("simple", None),
+            ProjectType::Workspace => ("workspace", None),
+            ProjectType::WorkspaceMember { .. } => ("workspace-member", None),
+            ProjectType::MultiComponent { component_path } => {
+                ("multi-component", Some(component_path.clone()))
+            }
+        };
+        Ok(DetectionReport {
+            project_type: label,
+            component,
+            binaries,
+            build_dir: project_type.build_dir(&self.config.project_path),
+        })
+    }
+}
+
+impl DetectionReport {
+    /// Formats the report for the terminal, marking sources not yet built.
+    pub fn render(&self, project_path: &Path) -> String {
+        let mut out = format!("Project: {}\n", project_path.display());
+        let _ = writeln!(out, "Type: {}", self.project_type);
+        if let Some(ref component) = self.component {
+            let shown = component.strip_prefix(project_path).unwrap_or(component);
+            let _ = writeln!(out, "Component: {}", shown.display());
+        }
+        let _ = writeln!(out, "Build dir: {}", self.build_dir.display());
+        let _ = writeln!(out, "Binaries:");
+        for (name, source) in &self.binaries {
+            let missing = if source.exists() { "" } else { " (not built)" };
+            let _ = writeln!(out, "  {name} -> {}{missing}", source.display());
+        }
+        out
+    }
+}
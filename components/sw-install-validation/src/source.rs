@@ -16,15 +16,16 @@ pub(crate) fn validate_source_binaries(
     for name in binary_names {
         let (source_path, source_root) = get_source_paths(validator, name, project_type);
         if !source_path.exists() {
-            return Err(InstallError::BinaryNotFound(source_path.to_path_buf()));
+            return Err(missing_binary_error(validator, name, source_path));
         }
-        check_freshness(&source_path, &source_root)?;
+        check_not_empty(&source_path)?;
+        check_freshness(validator, &source_path, &source_root)?;
         results.push((name.clone(), source_path));
     }
     Ok(results)
 }
 
-fn get_source_paths(
+pub(crate) fn get_source_paths(
     validator: &Validator,
     binary_name: &str,
     project_type: &ProjectType,
@@ -34,28 +35,69 @@ fn get_source_paths(
             validator.config.source_binary_path(binary_name),
             validator.config.project_path.clone(),
         ),
-        ProjectType::MultiComponent { component_path } => {
-            let subdir = if validator.config.use_debug {
-                "debug"
-            } else {
-                "release"
-            };
-            (
-                component_path.join("target").join(subdir).join(binary_name),
-                component_path.clone(),
-            )
-        }
+        ProjectType::WorkspaceMember { workspace_root } => (
+            validator
+                .config
+                .output_dir(workspace_root)
+                .join(binary_name),
+            validator.config.project_path.clone(),
+        ),
+        ProjectType::MultiComponent { component_path } => (
+            validator
+                .config
+                .output_dir(component_path)
+                .join(binary_name),
+            component_path.clone(),
+        ),
+    }
+}
+
+fn missing_binary_error(validator: &Validator, name: &str, source_path: PathBuf) -> InstallError {
+    // Dry runs are for checking the setup, so spell out what was checked
+    if validator.config.dry_run {
+        let profile = validator.config.profile();
+        validator.output.warn(&format!(
+            "Looked for {name} ({profile} build) at {}",
+            source_path.display()
+        ));
+    }
+    if validator.config.example.is_some() {
+        InstallError::ExampleNotFound(name.to_string(), source_path)
+    } else {
+        InstallError::BinaryNotFound(source_path)
     }
 }
 
-fn check_freshness(source_path: &Path, source_root: &Path) -> Result<()> {
-    let binary_time = fs::metadata(source_path)
-        .and_then(|m| m.modified())
-        .unwrap_or(SystemTime::UNIX_EPOCH);
-    if let Some(source_time) = find_newest_source_file(source_root)
-        && source_time > binary_time
+/// Rejects zero-byte binaries left behind by failed or interrupted builds.
+fn check_not_empty(source_path: &Path) -> Result<()> {
+    if fs::metadata(source_path)?.len() == 0 {
+        return Err(InstallError::EmptyBinary(source_path.to_path_buf()));
+    }
+    Ok(())
+}
+
+/// Fails on a binary older than its sources, or only warns under
+/// `--allow-outdated`.
+fn check_freshness(validator: &Validator, source_path: &Path, source_root: &Path) -> Result<()> {
+    let tolerance = validator.config.freshness_tolerance;
+    // Without a binary mtime there is nothing to compare against; treating
+    // it as the epoch would flag every binary as outdated
+    let Ok(binary_time) = fs::metadata(source_path).and_then(|m| m.modified()) else {
+        return Ok(());
+    };
+    let newest_source = validator
+        .output
+        .timed("freshness", || find_newest_source_file(source_root));
+    if let Some(source_time) = newest_source
+        && source_time > binary_time + tolerance
     {
-        return Err(InstallError::BinaryOutdated(source_path.to_path_buf()));
+        if !validator.config.allow_outdated {
+            return Err(InstallError::BinaryOutdated(source_path.to_path_buf()));
+        }
+        validator.output.warn(&format!(
+            "Binary is older than source files: {}",
+            source_path.display()
+        ));
     }
     Ok(())
 }
@@ -0,0 +1,112 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// What a single workspace member contributes to installation.
+pub(crate) enum MemberKind {
+    Binaries(Vec<String>),
+    Library(String),
+    Unreadable,
+}
+
+pub(crate) fn expand_member_paths(root: &Path, member: &str) -> Vec<PathBuf> {
+    if let Some(base) = member.strip_suffix("/*") {
+        // read_dir order varies by filesystem; sort for reproducible scans
+        let mut paths: Vec<PathBuf> = fs::read_dir(root.join(base))
+            .map(|e| {
+                e.filter_map(|e| e.ok())
+                    .filter(|e| e.path().is_dir())
+                    .map(|e| PathBuf::from(base).join(e.file_name()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        paths.sort();
+        paths
+    } else {
+        vec![PathBuf::from(member)]
+    }
+}
+
+pub(crate) fn classify_member(root: &Path, path: &Path) -> MemberKind {
+    let dir = root.join(path);
+    let Ok(contents) = fs::read_to_string(dir.join("Cargo.toml")) else {
+        return MemberKind::Unreadable;
+    };
+    let Ok(value) = toml::from_str::<toml::Value>(&contents) else {
+        return MemberKind::Unreadable;
+    };
+    let name = value
+        .get("package")
+        .and_then(|pkg| pkg.get("name"))
+        .and_then(|n| n.as_str());
+    let declared = value.get("bin").and_then(|b| b.as_array());
+    let mut names: Vec<String> = match declared {
+        Some(bins) => bins
+            .iter()
+            .filter_map(|b| b.get("name").and_then(|n| n.as_str()))
+            .map(String::from)
+            .collect(),
+        None => name
+            .filter(|_| dir.join("src/main.rs").exists())
+            .map(String::from)
+            .into_iter()
+            .collect(),
+    };
+    merge_names(&mut names, auto_bin_names(&dir, &value));
+    match name {
+        _ if declared.is_some() || !names.is_empty() => MemberKind::Binaries(names),
+        Some(name) => MemberKind::Library(name.to_string()),
+        None => MemberKind::Unreadable,
+    }
+}
+
+/// Binaries cargo discovers in `src/bin/` (`<name>.rs` or `<name>/main.rs`),
+/// sorted. Empty when the package sets `autobins = false`; files already
+/// claimed by a `[[bin]]` path are left to that entry.
+pub fn auto_bin_names(dir: &Path, manifest: &toml::Value) -> Vec<String> {
+    let autobins = manifest
+        .get("package")
+        .and_then(|pkg| pkg.get("autobins"))
+        .and_then(|a| a.as_bool())
+        .unwrap_or(true);
+    let entries = match fs::read_dir(dir.join("src").join("bin")) {
+        Ok(entries) if autobins => entries,
+        _ => return Vec::new(),
+    };
+    let claimed = claimed_paths(dir, manifest);
+    let mut names: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let path = e.path();
+            let (file, name) = if path.is_dir() {
+                (path.join("main.rs"), path.file_name()?)
+            } else {
+                (path.clone(), path.file_stem()?)
+            };
+            let is_rust = file.extension().is_some_and(|x| x == "rs") && file.is_file();
+            (is_rust && !claimed.contains(&file)).then(|| name.to_string_lossy().into_owned())
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+fn claimed_paths(dir: &Path, manifest: &toml::Value) -> Vec<PathBuf> {
+    let bins = manifest.get("bin").and_then(|b| b.as_array());
+    bins.into_iter()
+        .flatten()
+        .filter_map(|b| b.get("path").and_then(|p| p.as_str()))
+        .map(|p| dir.join(p))
+        .collect()
+}
+
+/// Appends the `extra` names that `names` does not already contain.
+pub fn merge_names(names: &mut Vec<String>, extra: Vec<String>) {
+    for name in extra {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+}
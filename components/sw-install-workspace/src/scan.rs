@@ -0,0 +1,78 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use crate::member::{MemberKind, classify_member, expand_member_paths};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Result of scanning workspace members for installable binaries.
+#[derive(Debug, Default)]
+pub struct WorkspaceScan {
+    pub binaries: Vec<String>,
+    pub scanned: usize,
+    pub skipped_libraries: Vec<String>,
+    /// Binary names declared by more than one member, with those members'
+    /// paths relative to the workspace root.
+    pub duplicates: Vec<(String, Vec<PathBuf>)>,
+}
+
+impl WorkspaceScan {
+    /// Human-readable report of scanned members and skipped library crates.
+    pub fn summary(&self) -> String {
+        let p = |n: usize, one: &'static str, many: &'static str| if n == 1 { one } else { many };
+        let mut summary = format!(
+            "Scanned {} workspace {}",
+            self.scanned,
+            p(self.scanned, "member", "members")
+        );
+        let skipped = self.skipped_libraries.len();
+        if skipped > 0 {
+            summary.push_str(&format!(
+                "; skipped {} {}: {}",
+                skipped,
+                p(skipped, "library", "libraries"),
+                self.skipped_libraries.join(", ")
+            ));
+        }
+        summary
+    }
+}
+
+pub fn scan_workspace(root: &Path, members: &[toml::Value]) -> WorkspaceScan {
+    let mut scan = WorkspaceScan::default();
+    let mut owners: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    let paths = members
+        .iter()
+        .filter_map(|m| m.as_str())
+        .flat_map(|member| expand_member_paths(root, member));
+    for path in paths {
+        scan.scanned += 1;
+        match classify_member(root, &path) {
+            MemberKind::Binaries(names) => {
+                for name in names {
+                    owners.entry(name).or_default().push(path.clone());
+                }
+            }
+            MemberKind::Library(name) => scan.skipped_libraries.push(name),
+            MemberKind::Unreadable => {}
+        }
+    }
+    // Installs and messages follow name order, not member declaration order
+    scan.binaries = owners.keys().cloned().collect();
+    scan.duplicates = owners.into_iter().filter(|(_, p)| p.len() > 1).collect();
+    scan.skipped_libraries.sort();
+    scan
+}
+
+/// Binaries built by the package rooted at `dir`, or `None` for a library
+/// or a directory without a readable package manifest.
+pub fn package_binaries(dir: &Path) -> Option<Vec<String>> {
+    match classify_member(dir, Path::new("")) {
+        MemberKind::Binaries(names) if !names.is_empty() => Some(names),
+        _ => None,
+    }
+}
+
+pub fn find_workspace_binaries(root: &Path, members: &[toml::Value]) -> Vec<String> {
+    scan_workspace(root, members).binaries
+}
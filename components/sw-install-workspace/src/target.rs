@@ -0,0 +1,39 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+use std::path::{Path, PathBuf};
+
+/// The Cargo project whose `target/` dir holds `binary`, e.g. for a binary
+/// run straight out of `cargo build` or `cargo run`. `None` once the binary
+/// has been copied anywhere else.
+pub fn project_for_binary(binary: &Path) -> Option<PathBuf> {
+    binary
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.file_name().is_some_and(|n| n == "target"))
+        .and_then(Path::parent)
+        .filter(|project| project.join("Cargo.toml").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// The workspace root above `member` that holds the shared `target/` dir,
+/// for a member crate that has no `target/` of its own because cargo builds
+/// workspace members into the root's.
+pub fn enclosing_workspace(member: &Path) -> Option<PathBuf> {
+    if member.join("target").is_dir() {
+        return None;
+    }
+    member
+        .ancestors()
+        .skip(1)
+        .find(|dir| is_workspace_root(dir))
+        .filter(|root| root.join("target").is_dir())
+        .map(Path::to_path_buf)
+}
+
+fn is_workspace_root(dir: &Path) -> bool {
+    std::fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|contents| toml::from_str::<toml::Value>(&contents).ok())
+        .is_some_and(|value| value.get("workspace").is_some())
+}
@@ -0,0 +1,184 @@
+// Copyright (c) 2025 Michael A Wright
+// Licensed under the MIT License
+
+//! Tests for workspace member scanning.
+
+use std::fs;
+use std::path::Path;
+use sw_install_workspace::{enclosing_workspace, project_for_binary, scan_workspace};
+use tempfile::TempDir;
+
+fn create_member(root: &Path, name: &str, is_bin: bool) {
+    let dir = root.join("crates").join(name);
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(
+        dir.join("Cargo.toml"),
+        format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n"),
+    )
+    .unwrap();
+    let file = if is_bin { "main.rs" } else { "lib.rs" };
+    fs::write(dir.join("src").join(file), "").unwrap();
+}
+
+fn members(names: &[&str]) -> Vec<toml::Value> {
+    names
+        .iter()
+        .map(|n| toml::Value::String(format!("crates/{n}")))
+        .collect()
+}
+
+#[test]
+fn test_scan_reports_skipped_libraries() {
+    let temp_dir = TempDir::new().unwrap();
+    create_member(temp_dir.path(), "core-lib", false);
+    create_member(temp_dir.path(), "util-lib", false);
+    create_member(temp_dir.path(), "my-cli", true);
+
+    let scan = scan_workspace(
+        temp_dir.path(),
+        &members(&["core-lib", "util-lib", "my-cli"]),
+    );
+    assert_eq!(scan.binaries, vec!["my-cli".to_string()]);
+    assert_eq!(scan.scanned, 3);
+    assert_eq!(scan.skipped_libraries, vec!["core-lib", "util-lib"]);
+    assert_eq!(
+        scan.summary(),
+        "Scanned 3 workspace members; skipped 2 libraries: core-lib, util-lib"
+    );
+}
+
+#[test]
+fn test_scan_summary_without_libraries() {
+    let temp_dir = TempDir::new().unwrap();
+    create_member(temp_dir.path(), "my-cli", true);
+
+    let scan = scan_workspace(temp_dir.path(), &members(&["my-cli"]));
+    assert_eq!(scan.summary(), "Scanned 1 workspace member");
+}
+
+#[test]
+fn test_scan_sorts_binaries_regardless_of_member_order() {
+    let temp_dir = TempDir::new().unwrap();
+    for name in ["zeta", "alpha", "mid"] {
+        create_member(temp_dir.path(), name, true);
+    }
+
+    let listed = scan_workspace(temp_dir.path(), &members(&["zeta", "alpha", "mid"]));
+    let globbed = scan_workspace(
+        temp_dir.path(),
+        &[toml::Value::String("crates/*".to_string())],
+    );
+    assert_eq!(listed.binaries, vec!["alpha", "mid", "zeta"]);
+    assert_eq!(globbed.binaries, listed.binaries);
+}
+
+fn write_bin_target(dir: &Path, file: &str) {
+    let path = dir.join("src").join("bin").join(file);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, "fn main() {}").unwrap();
+}
+
+#[test]
+fn test_src_bin_targets_are_discovered() {
+    let temp_dir = TempDir::new().unwrap();
+    create_member(temp_dir.path(), "tool", true);
+    let dir = temp_dir.path().join("crates").join("tool");
+    write_bin_target(&dir, "extra.rs");
+    write_bin_target(&dir, "helper/main.rs");
+
+    let names = sw_install_workspace::package_binaries(&dir).unwrap();
+    assert_eq!(names, vec!["tool", "extra", "helper"]);
+}
+
+#[test]
+fn test_autobins_false_ignores_stray_src_bin_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"pkg\"\nversion = \"0.1.0\"\nautobins = false\n\n\
+         [[bin]]\nname = \"declared\"\npath = \"src/main.rs\"\n",
+    )
+    .unwrap();
+    write_bin_target(dir, "extra.rs");
+
+    let names = sw_install_workspace::package_binaries(dir).unwrap();
+    assert_eq!(names, vec!["declared"]);
+}
+
+#[test]
+fn test_scan_reports_binary_names_shared_by_members() {
+    let temp_dir = TempDir::new().unwrap();
+    for member in ["a", "b"] {
+        create_member(temp_dir.path(), member, true);
+        let manifest = temp_dir
+            .path()
+            .join("crates")
+            .join(member)
+            .join("Cargo.toml");
+        let mut contents = fs::read_to_string(&manifest).unwrap();
+        contents.push_str("\n[[bin]]\nname = \"cli\"\npath = \"src/main.rs\"\n");
+        fs::write(&manifest, contents).unwrap();
+    }
+    create_member(temp_dir.path(), "other", true);
+
+    let scan = scan_workspace(temp_dir.path(), &members(&["a", "b", "other"]));
+    assert_eq!(scan.binaries, vec!["cli".to_string(), "other".to_string()]);
+    assert_eq!(
+        scan.duplicates,
+        vec![(
+            "cli".to_string(),
+            vec![
+                Path::new("crates/a").to_path_buf(),
+                Path::new("crates/b").to_path_buf()
+            ]
+        )]
+    );
+}
+
+#[test]
+fn test_project_for_binary_finds_checkout_around_target_dir() {
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path().join("sw-install-cli");
+    fs::create_dir_all(project.join("target").join("release")).unwrap();
+    fs::write(
+        project.join("Cargo.toml"),
+        "[package]\nname = \"sw-install\"\n",
+    )
+    .unwrap();
+
+    let release = project.join("target").join("release").join("sw-install");
+    assert_eq!(project_for_binary(&release), Some(project.clone()));
+    let deps = project.join("target/debug/deps/sw-install-1234");
+    assert_eq!(project_for_binary(&deps), Some(project.clone()));
+}
+
+#[test]
+fn test_project_for_binary_rejects_unknown_locations() {
+    let temp_dir = TempDir::new().unwrap();
+    let installed = temp_dir.path().join(".local/bin/sw-install");
+    assert_eq!(project_for_binary(&installed), None);
+    // A target/ dir alone is not a project without its Cargo.toml
+    let orphan = temp_dir.path().join("target/release/sw-install");
+    assert_eq!(project_for_binary(&orphan), None);
+}
+
+#[test]
+fn test_enclosing_workspace_only_when_member_has_no_target() {
+    let temp = TempDir::new().unwrap();
+    let root = temp.path();
+    fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"cli\"]\n",
+    )
+    .unwrap();
+    let member = root.join("cli");
+    fs::create_dir_all(&member).unwrap();
+    assert_eq!(enclosing_workspace(&member), None);
+
+    fs::create_dir_all(root.join("target")).unwrap();
+    assert_eq!(enclosing_workspace(&member), Some(root.to_path_buf()));
+
+    fs::create_dir_all(member.join("target")).unwrap();
+    assert_eq!(enclosing_workspace(&member), None);
+}
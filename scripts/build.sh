@@ -21,6 +21,10 @@ cd "$REPO_ROOT/components/sw-install-validation"
 echo "Building sw-install-validation..."
 cargo build --release
 
+cd "$REPO_ROOT/components/sw-install-manifest"
+echo "Building sw-install-manifest..."
+cargo build --release
+
 cd "$REPO_ROOT/components/sw-install-installer"
 echo "Building sw-install-installer..."
 cargo build --release
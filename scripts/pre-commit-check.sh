@@ -12,6 +12,7 @@ COMPONENTS_DIR="$PROJECT_ROOT/components"
 COMPONENTS=(
     "sw-install-core"
     "sw-install-workspace"
+    "sw-install-manifest"
     "sw-install-validation"
     "sw-install-installer"
     "sw-install-manage"